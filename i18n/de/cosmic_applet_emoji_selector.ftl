@@ -10,4 +10,151 @@ flags = Flaggen
 
 emojis-and-favorites = Emojis und Favoriten
 
-search-for-emojis = Nach Emojis suchen...
\ No newline at end of file
+search-for-emojis = Nach Emojis suchen...
+
+copy = Kopieren
+
+format-emoji = Emoji
+format-shortcode = Kurzcode
+format-codepoint = Codepoint
+format-html-entity = HTML-Entität
+format-escape = Escape-Sequenz
+format-url-encoded = URL-kodiert
+
+copy-image = Als Bild kopieren
+save-image = Als Bild speichern
+
+presentation-as-is = Unverändert
+presentation-emoji = Emoji
+presentation-text = Text
+
+copied = Kopiert
+
+subgroup-face-smiling = Lächelnde Gesichter
+subgroup-face-affection = Liebevolle Gesichter
+subgroup-face-tongue = Gesichter mit Zunge
+subgroup-face-hand = Gesichter mit Hand
+subgroup-face-neutral-skeptical = Neutrale und skeptische Gesichter
+subgroup-face-sleepy = Schläfrige Gesichter
+subgroup-face-unwell = Kranke Gesichter
+subgroup-face-hat = Gesichter mit Hut
+subgroup-face-glasses = Gesichter mit Brille
+subgroup-face-concerned = Besorgte Gesichter
+subgroup-face-negative = Negative Gesichter
+subgroup-face-costume = Kostümierte Gesichter
+subgroup-cat-face = Katzengesichter
+subgroup-monkey-face = Affengesichter
+subgroup-heart = Herzen
+subgroup-emotion = Emotionen
+subgroup-hand-fingers-open = Offene Hände
+subgroup-hand-fingers-partial = Handzeichen
+subgroup-hand-single-finger = Einzelner Finger
+subgroup-hand-fingers-closed = Geschlossene Hände
+subgroup-hands = Hände
+subgroup-hand-prop = Hände mit Gegenständen
+subgroup-body-parts = Körperteile
+subgroup-person = Personen
+subgroup-person-gesture = Gesten
+subgroup-person-role = Berufe und Rollen
+subgroup-person-fantasy = Fantasie
+subgroup-person-activity = Aktivitäten
+subgroup-person-sport = Sport
+subgroup-person-resting = Ausruhen
+subgroup-family = Familien
+subgroup-person-symbol = Personensymbole
+subgroup-animal-mammal = Säugetiere
+subgroup-animal-bird = Vögel
+subgroup-animal-amphibian = Amphibien
+subgroup-animal-reptile = Reptilien
+subgroup-animal-marine = Meerestiere
+subgroup-animal-bug = Insekten
+subgroup-plant-flower = Blumen
+subgroup-plant-other = Pflanzen
+subgroup-food-fruit = Obst
+subgroup-food-vegetable = Gemüse
+subgroup-food-prepared = Zubereitetes Essen
+subgroup-food-asian = Asiatisches Essen
+subgroup-food-marine = Meeresfrüchte
+subgroup-food-sweet = Süßes
+subgroup-drink = Getränke
+subgroup-dishware = Geschirr
+subgroup-place-map = Karten
+subgroup-place-geographic = Geografische Orte
+subgroup-place-building = Gebäude
+subgroup-place-religious = Religiöse Orte
+subgroup-place-other = Andere Orte
+subgroup-transport-ground = Landverkehr
+subgroup-transport-water = Wasserverkehr
+subgroup-transport-air = Luftverkehr
+subgroup-hotel = Hotel
+subgroup-time = Zeit
+subgroup-sky-and-weather = Himmel und Wetter
+subgroup-event = Ereignisse
+subgroup-award-medal = Auszeichnungen und Medaillen
+subgroup-sport = Sport
+subgroup-game = Spiele
+subgroup-arts-and-crafts = Kunst und Handwerk
+subgroup-clothing = Kleidung
+subgroup-sound = Ton
+subgroup-music = Musik
+subgroup-musical-instrument = Musikinstrumente
+subgroup-phone = Telefone
+subgroup-computer = Computer
+subgroup-light-and-video = Licht und Video
+subgroup-book-paper = Bücher und Papier
+subgroup-money = Geld
+subgroup-mail = Post
+subgroup-writing = Schreiben
+subgroup-office = Büro
+subgroup-lock = Schlösser
+subgroup-tool = Werkzeuge
+subgroup-science = Wissenschaft
+subgroup-medical = Medizin
+subgroup-household = Haushalt
+subgroup-other-object = Andere Gegenstände
+subgroup-transport-sign = Verkehrszeichen
+subgroup-warning = Warnungen
+subgroup-arrow = Pfeile
+subgroup-religion = Religion
+subgroup-zodiac = Sternzeichen
+subgroup-av-symbol = Mediensymbole
+subgroup-gender = Geschlecht
+subgroup-math = Mathematik
+subgroup-punctuation = Satzzeichen
+subgroup-currency = Währungen
+subgroup-other-symbol = Andere Symbole
+subgroup-keycap = Tasten
+subgroup-alphanum = Buchstaben und Zahlen
+subgroup-geometric = Geometrische Formen
+subgroup-flag = Flaggen
+subgroup-country-flag = Länderflaggen
+subgroup-subdivision-flag = Regionalflaggen
+
+kaomoji = Kaomoji
+
+custom = Eigene
+
+unicode-symbols = Unicode-Zeichen
+search-for-symbols = Nach Zeichen suchen...
+
+zwj-join = Verbinden
+zwj-valid = Empfohlene Sequenz
+zwj-invalid = Keine empfohlene Sequenz
+
+region-africa = Afrika
+region-americas = Amerika
+region-antarctica = Antarktis
+region-asia = Asien
+region-europe = Europa
+region-oceania = Ozeanien
+
+letters-regional-indicator = Regionale Indikatoren
+letters-squared = Quadratische Buchstaben
+type-text = Text eingeben...
+
+paste-text = Text einfügen...
+
+inspect-unknown = Unbekanntes Zeichen
+inspect-zwj = ZWJ-Sequenz
+inspect-emoji-presentation = Emoji-Darstellung
+inspect-text-presentation = Textdarstellung
\ No newline at end of file
@@ -10,4 +10,151 @@ flags = Flags
 
 emojis-and-favorites = Emojis and Favorites
 
-search-for-emojis = Search for Emojis...
\ No newline at end of file
+search-for-emojis = Search for Emojis...
+
+copy = Copy
+
+format-emoji = Emoji
+format-shortcode = Shortcode
+format-codepoint = Codepoint
+format-html-entity = HTML entity
+format-escape = Escape sequence
+format-url-encoded = URL encoded
+
+copy-image = Copy as image
+save-image = Save as image
+
+presentation-as-is = As is
+presentation-emoji = Emoji
+presentation-text = Text
+
+copied = Copied
+
+subgroup-face-smiling = Smiling faces
+subgroup-face-affection = Affectionate faces
+subgroup-face-tongue = Faces with tongue
+subgroup-face-hand = Faces with hand
+subgroup-face-neutral-skeptical = Neutral and skeptical faces
+subgroup-face-sleepy = Sleepy faces
+subgroup-face-unwell = Unwell faces
+subgroup-face-hat = Faces with hat
+subgroup-face-glasses = Faces with glasses
+subgroup-face-concerned = Concerned faces
+subgroup-face-negative = Negative faces
+subgroup-face-costume = Costume faces
+subgroup-cat-face = Cat faces
+subgroup-monkey-face = Monkey faces
+subgroup-heart = Hearts
+subgroup-emotion = Emotions
+subgroup-hand-fingers-open = Open hands
+subgroup-hand-fingers-partial = Hand signs
+subgroup-hand-single-finger = Single finger
+subgroup-hand-fingers-closed = Closed hands
+subgroup-hands = Hands
+subgroup-hand-prop = Hands with objects
+subgroup-body-parts = Body parts
+subgroup-person = People
+subgroup-person-gesture = Gestures
+subgroup-person-role = Roles
+subgroup-person-fantasy = Fantasy
+subgroup-person-activity = Activities
+subgroup-person-sport = Sports
+subgroup-person-resting = Resting
+subgroup-family = Families
+subgroup-person-symbol = Person symbols
+subgroup-animal-mammal = Mammals
+subgroup-animal-bird = Birds
+subgroup-animal-amphibian = Amphibians
+subgroup-animal-reptile = Reptiles
+subgroup-animal-marine = Marine animals
+subgroup-animal-bug = Bugs
+subgroup-plant-flower = Flowers
+subgroup-plant-other = Plants
+subgroup-food-fruit = Fruit
+subgroup-food-vegetable = Vegetables
+subgroup-food-prepared = Prepared food
+subgroup-food-asian = Asian food
+subgroup-food-marine = Seafood
+subgroup-food-sweet = Sweets
+subgroup-drink = Drinks
+subgroup-dishware = Dishware
+subgroup-place-map = Maps
+subgroup-place-geographic = Geographic places
+subgroup-place-building = Buildings
+subgroup-place-religious = Religious places
+subgroup-place-other = Other places
+subgroup-transport-ground = Ground transport
+subgroup-transport-water = Water transport
+subgroup-transport-air = Air transport
+subgroup-hotel = Hotel
+subgroup-time = Time
+subgroup-sky-and-weather = Sky and weather
+subgroup-event = Events
+subgroup-award-medal = Awards and medals
+subgroup-sport = Sports
+subgroup-game = Games
+subgroup-arts-and-crafts = Arts and crafts
+subgroup-clothing = Clothing
+subgroup-sound = Sound
+subgroup-music = Music
+subgroup-musical-instrument = Musical instruments
+subgroup-phone = Phones
+subgroup-computer = Computers
+subgroup-light-and-video = Light and video
+subgroup-book-paper = Books and paper
+subgroup-money = Money
+subgroup-mail = Mail
+subgroup-writing = Writing
+subgroup-office = Office
+subgroup-lock = Locks
+subgroup-tool = Tools
+subgroup-science = Science
+subgroup-medical = Medical
+subgroup-household = Household
+subgroup-other-object = Other objects
+subgroup-transport-sign = Transport signs
+subgroup-warning = Warnings
+subgroup-arrow = Arrows
+subgroup-religion = Religion
+subgroup-zodiac = Zodiac
+subgroup-av-symbol = Media symbols
+subgroup-gender = Gender
+subgroup-math = Math
+subgroup-punctuation = Punctuation
+subgroup-currency = Currency
+subgroup-other-symbol = Other symbols
+subgroup-keycap = Keycaps
+subgroup-alphanum = Letters and numbers
+subgroup-geometric = Geometric shapes
+subgroup-flag = Flags
+subgroup-country-flag = Country flags
+subgroup-subdivision-flag = Subdivision flags
+
+kaomoji = Kaomoji
+
+custom = Custom
+
+unicode-symbols = Unicode Symbols
+search-for-symbols = Search for Symbols...
+
+zwj-join = Join
+zwj-valid = Recommended sequence
+zwj-invalid = Not a recommended sequence
+
+region-africa = Africa
+region-americas = Americas
+region-antarctica = Antarctica
+region-asia = Asia
+region-europe = Europe
+region-oceania = Oceania
+
+letters-regional-indicator = Regional indicators
+letters-squared = Squared letters
+type-text = Type text...
+
+paste-text = Paste text...
+
+inspect-unknown = Unknown character
+inspect-zwj = ZWJ sequence
+inspect-emoji-presentation = Emoji presentation
+inspect-text-presentation = Text presentation
\ No newline at end of file
@@ -0,0 +1,523 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+use std::process::Stdio;
+
+use cosmic::iced::clipboard::mime::AsMimeTypes;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+
+/// Placeholder in custom command templates, replaced with the copied text.
+pub const PLACEHOLDER: &str = "{emoji}";
+/// Placeholder in custom command templates, replaced with `clipboard` or `primary`.
+pub const SELECTION_PLACEHOLDER: &str = "{selection}";
+
+/// The Wayland selection to write to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    Clipboard,
+    /// Pasted with a middle-click.
+    Primary,
+}
+
+/// Which selections a copy writes to.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Default, Clone, Copy)]
+pub enum CopyTarget {
+    #[default]
+    Clipboard,
+    Primary,
+    Both,
+}
+
+impl CopyTarget {
+    pub fn selections(self) -> &'static [Selection] {
+        match self {
+            CopyTarget::Clipboard => &[Selection::Clipboard],
+            CopyTarget::Primary => &[Selection::Primary],
+            CopyTarget::Both => &[Selection::Clipboard, Selection::Primary],
+        }
+    }
+}
+
+/// Ways to copy text, tried in order until one succeeds.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Clone)]
+pub enum Backend {
+    /// The clipboard of the applet itself.
+    Iced,
+    WlCopy,
+    Xclip,
+    Xsel,
+    /// A command such as `mycopy --text {emoji}`, run with [`run_command`].
+    Command(String),
+}
+
+impl Backend {
+    /// Copies `contents` with an external tool, the iced clipboard is written by the applet.
+    pub async fn copy(
+        &self,
+        contents: &Contents,
+        selection: Selection,
+        wl_copy_path: &str,
+    ) -> io::Result<()> {
+        match contents {
+            Contents::Text { text, .. } => self.copy_text(text, selection, wl_copy_path).await,
+            Contents::Data(data) => self.copy_data(data, wl_copy_path).await,
+        }
+    }
+
+    async fn copy_text(
+        &self,
+        text: &str,
+        selection: Selection,
+        wl_copy_path: &str,
+    ) -> io::Result<()> {
+        let primary = selection == Selection::Primary;
+        let stdin = Some(text.as_bytes());
+        match self {
+            Backend::Iced => Err(written_by_applet()),
+            Backend::WlCopy if primary => run(wl_copy_path, &["--primary"], stdin).await,
+            Backend::WlCopy => run(wl_copy_path, &[], stdin).await,
+            Backend::Xclip if primary => run("xclip", &["-selection", "primary"], stdin).await,
+            Backend::Xclip => run("xclip", &["-selection", "clipboard"], stdin).await,
+            Backend::Xsel if primary => run("xsel", &["--primary", "--input"], stdin).await,
+            Backend::Xsel => run("xsel", &["--clipboard", "--input"], stdin).await,
+            Backend::Command(template) => {
+                let selection = if primary { "primary" } else { "clipboard" };
+                run_command(template, text, &[(SELECTION_PLACEHOLDER, selection)]).await
+            }
+        }
+    }
+
+    /// Copies `data` such as an image to the clipboard selection.
+    ///
+    /// Only the iced clipboard offers more than one MIME type, the others use the first one.
+    async fn copy_data(&self, data: &Data, wl_copy_path: &str) -> io::Result<()> {
+        let Some((mime, bytes)) = data.types.first() else {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "no data"));
+        };
+        let mime = mime.as_str();
+        match self {
+            Backend::Iced => Err(written_by_applet()),
+            Backend::WlCopy => run(wl_copy_path, &["--type", mime], Some(bytes)).await,
+            Backend::Xclip => {
+                let args = ["-selection", "clipboard", "-t", mime];
+                run("xclip", &args, Some(bytes)).await
+            }
+            Backend::Xsel | Backend::Command(_) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("{mime} is not supported"),
+            )),
+        }
+    }
+}
+
+fn written_by_applet() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "the iced clipboard is written by the applet",
+    )
+}
+
+/// What is copied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Contents {
+    /// Offered as HTML as well by the iced clipboard, if `html` is set.
+    Text { text: String, html: Option<String> },
+    /// Only copied to the clipboard selection.
+    Data(Data),
+}
+
+/// A copy to each selection, trying the backends in order until one succeeds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub contents: Contents,
+    /// Whether the popup is closed once all selections are written.
+    pub close: bool,
+    backends: Vec<Backend>,
+    // the selections left to write, the first one with the backend at `backend`
+    selections: Vec<Selection>,
+    backend: usize,
+}
+
+// the next thing to do for a `Request`
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    /// Run the backend, followed by `Request::finish` with the result.
+    Run(Backend, Selection),
+    /// Write the selection with the iced clipboard.
+    Iced(Selection),
+    Finished,
+}
+
+impl Request {
+    pub fn new(
+        contents: Contents,
+        target: CopyTarget,
+        backends: Vec<Backend>,
+        close: bool,
+    ) -> Self {
+        let selections = match contents {
+            Contents::Text { .. } => target.selections().to_vec(),
+            Contents::Data(_) => vec![Selection::Clipboard],
+        };
+        Self {
+            contents,
+            close,
+            backends,
+            selections,
+            backend: 0,
+        }
+    }
+
+    /// Steps until a backend has to run, returning the selections to write with the
+    /// iced clipboard and the backend to run with `Request::copy`, if any is left.
+    pub fn advance(&mut self) -> (Vec<Selection>, Option<(Backend, Selection)>) {
+        let mut iced = Vec::new();
+        loop {
+            match self.step() {
+                Step::Run(backend, selection) => return (iced, Some((backend, selection))),
+                Step::Iced(selection) => iced.push(selection),
+                Step::Finished => return (iced, None),
+            }
+        }
+    }
+
+    /// Runs a backend returned by `advance` and moves on according to the result.
+    pub async fn copy(
+        mut self,
+        backend: Backend,
+        selection: Selection,
+        wl_copy_path: String,
+    ) -> (Self, Result<(), String>) {
+        let res = backend
+            .copy(&self.contents, selection, &wl_copy_path)
+            .await
+            .map_err(|err| format!("{backend}: {err}"));
+        self.finish(res.is_ok());
+        (self, res)
+    }
+
+    // once all external backends failed, the iced clipboard is used
+    fn step(&mut self) -> Step {
+        let Some(&selection) = self.selections.first() else {
+            return Step::Finished;
+        };
+        match self.backends.get(self.backend) {
+            Some(Backend::Iced) | None => {
+                self.finish(true);
+                Step::Iced(selection)
+            }
+            Some(backend) => Step::Run(backend.clone(), selection),
+        }
+    }
+
+    // moves on to the next selection, or to the next backend if the copy failed
+    fn finish(&mut self, copied: bool) {
+        if copied {
+            if !self.selections.is_empty() {
+                self.selections.remove(0);
+            }
+            self.backend = 0;
+        } else {
+            self.backend += 1;
+        }
+    }
+}
+
+// the text targets offered by wl-copy, X11 applications ask for the upper case ones
+const TEXT_TYPES: &[&str] = &[
+    "text/plain;charset=utf-8",
+    "text/plain",
+    "UTF8_STRING",
+    "STRING",
+    "TEXT",
+];
+
+/// Clipboard contents offered as one or more MIME types, in order of preference.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Data {
+    pub types: Vec<(String, Vec<u8>)>,
+}
+
+impl Data {
+    pub fn new(mime: impl Into<String>, bytes: Vec<u8>) -> Self {
+        Self::default().with(mime, bytes)
+    }
+
+    pub fn with(mut self, mime: impl Into<String>, bytes: Vec<u8>) -> Self {
+        self.types.push((mime.into(), bytes));
+        self
+    }
+
+    /// Text with the targets applications paste plain text from, and `html` for rich text.
+    pub fn text(text: &str, html: Option<&str>) -> Self {
+        let mut data = Self::default();
+        for mime in TEXT_TYPES {
+            data = data.with(*mime, text.as_bytes().to_vec());
+        }
+        match html {
+            Some(html) => data.with("text/html", html.as_bytes().to_vec()),
+            None => data,
+        }
+    }
+}
+
+impl AsMimeTypes for Data {
+    fn available(&self) -> Cow<'static, [String]> {
+        Cow::Owned(self.types.iter().map(|(mime, _)| mime.clone()).collect())
+    }
+
+    fn as_bytes(&self, mime_type: &str) -> Option<Cow<'static, [u8]>> {
+        self.types
+            .iter()
+            .find(|(mime, _)| mime == mime_type)
+            .map(|(_, bytes)| Cow::Owned(bytes.clone()))
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Backend::Iced => write!(f, "iced"),
+            Backend::WlCopy => write!(f, "wl-copy"),
+            Backend::Xclip => write!(f, "xclip"),
+            Backend::Xsel => write!(f, "xsel"),
+            Backend::Command(template) => write!(f, "`{template}`"),
+        }
+    }
+}
+
+/// Splits a command template into the program and its arguments and replaces the placeholders.
+pub fn expand_template(
+    template: &str,
+    replacements: &[(&str, &str)],
+) -> io::Result<(String, Vec<String>)> {
+    let mut parts = template.split_whitespace().map(|part| {
+        replacements
+            .iter()
+            .fold(part.to_string(), |part, (from, to)| part.replace(from, to))
+    });
+    let Some(program) = parts.next() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "command template is empty",
+        ));
+    };
+    Ok((program, parts.collect()))
+}
+
+/// Runs a command template with `text` replacing the placeholder, like [`run_template`].
+///
+/// Without a placeholder the text is written to stdin.
+pub async fn run_command(
+    template: &str,
+    text: &str,
+    replacements: &[(&str, &str)],
+) -> io::Result<()> {
+    let stdin = (!template.contains(PLACEHOLDER)).then_some(text.as_bytes());
+    // the text is replaced last, so that it is never expanded itself
+    let replacements = [replacements, &[(PLACEHOLDER, text)]].concat();
+    run_template(template, &replacements, stdin).await
+}
+
+/// Runs a command template with the placeholders replaced and waits for it,
+/// optionally piping `stdin` into it.
+pub async fn run_template(
+    template: &str,
+    replacements: &[(&str, &str)],
+    stdin: Option<&[u8]>,
+) -> io::Result<()> {
+    let (program, args) = expand_template(template, replacements)?;
+    let args: Vec<_> = args.iter().map(String::as_str).collect();
+    run(&program, &args, stdin).await
+}
+
+// unlike the iced clipboard, tools like `wl-copy` keep serving the selection
+// after the popup is closed
+async fn run(program: &str, args: &[&str], stdin: Option<&[u8]>) -> io::Result<()> {
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => io::Error::new(
+                err.kind(),
+                format!("`{program}` not found, is it installed?"),
+            ),
+            _ => err,
+        })?;
+    // stdin has to be closed, otherwise the program waits for more input
+    if let (Some(mut child_stdin), Some(bytes)) = (child.stdin.take(), stdin) {
+        child_stdin.write_all(bytes).await?;
+    }
+    // clipboard tools fork to serve the selection, the parent exits once the text is read
+    let status = child.wait().await?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "`{program}` exited with {status}"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::future::Future;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::{Path, PathBuf};
+    use std::{env, fs};
+
+    // records its arguments and stdin next to itself
+    const FAKE_SCRIPT: &str = "#!/bin/sh\necho \"$@\" > \"$0.args\"\ncat > \"$0.stdin\"\n";
+
+    pub(crate) fn temp_dir(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("emoji-selector-{name}-{}", std::process::id()))
+    }
+
+    pub(crate) fn fake_command(dir: &Path, name: &str) -> PathBuf {
+        fs::create_dir_all(dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, FAKE_SCRIPT).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    pub(crate) fn recorded(command: &Path) -> (String, String) {
+        let read = |extension| fs::read_to_string(command.with_extension(extension)).unwrap();
+        (read("args"), read("stdin"))
+    }
+
+    pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build();
+        runtime.unwrap().block_on(future)
+    }
+
+    // runs the request like `Window::copy` and `Message::Copied`,
+    // returning the backends that ran and the iced selections
+    fn drive(mut request: Request) -> (Vec<(Backend, bool)>, Vec<Selection>) {
+        let (mut runs, mut iced) = (Vec::new(), Vec::new());
+        loop {
+            let (selections, run) = request.advance();
+            iced.extend(selections);
+            let Some((backend, selection)) = run else {
+                return (runs, iced);
+            };
+            let (next, res) = block_on(request.copy(backend.clone(), selection, "wl-copy".into()));
+            runs.push((backend, res.is_ok()));
+            request = next;
+        }
+    }
+
+    fn text(text: &str) -> Contents {
+        Contents::Text {
+            text: text.into(),
+            html: None,
+        }
+    }
+
+    #[test]
+    fn wl_copy_pipes_text() {
+        let dir = temp_dir("wl-copy");
+        let wl_copy = fake_command(&dir, "wl-copy");
+        // like `wl_copy_path` in the config
+        let wl_copy_path = wl_copy.to_str().unwrap();
+        let crab = text("🦀");
+        let clipboard = Backend::WlCopy.copy(&crab, Selection::Clipboard, wl_copy_path);
+        assert!(block_on(clipboard).is_ok());
+        assert_eq!(recorded(&wl_copy), ("\n".into(), "🦀".into()));
+        let primary = Backend::WlCopy.copy(&crab, Selection::Primary, wl_copy_path);
+        assert!(block_on(primary).is_ok());
+        assert_eq!(recorded(&wl_copy), ("--primary\n".into(), "🦀".into()));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn iced_is_written_without_running_a_backend() {
+        let backends = vec![Backend::Iced, Backend::Xsel];
+        let request = Request::new(text("🦀"), CopyTarget::Both, backends, true);
+        let (runs, iced) = drive(request);
+        assert!(runs.is_empty());
+        assert_eq!(iced, [Selection::Clipboard, Selection::Primary]);
+    }
+
+    #[test]
+    fn failed_backends_fall_back_to_the_next() {
+        let dir = temp_dir("fallback");
+        let fake = fake_command(&dir, "fake-copy");
+        let missing = Backend::Command(dir.join("missing").display().to_string());
+        let fake_backend = Backend::Command(format!("{} {SELECTION_PLACEHOLDER}", fake.display()));
+        let backends = vec![missing.clone(), fake_backend.clone(), Backend::Iced];
+
+        let request = Request::new(text("🦀"), CopyTarget::Both, backends, true);
+        let (runs, iced) = drive(request);
+        let expected = [
+            (missing.clone(), false),
+            (fake_backend.clone(), true),
+            (missing, false),
+            (fake_backend, true),
+        ];
+        assert_eq!(runs, expected);
+        assert!(iced.is_empty());
+        assert_eq!(recorded(&fake), ("primary\n".into(), "🦀".into()));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn iced_is_the_last_resort() {
+        let fails = Backend::Command("false".into());
+        let backends = vec![fails.clone()];
+        let request = Request::new(text("🦀"), CopyTarget::Clipboard, backends, true);
+        let (runs, iced) = drive(request);
+        assert_eq!(runs, [(fails, false)]);
+        assert_eq!(iced, [Selection::Clipboard]);
+    }
+
+    #[test]
+    fn data_is_only_copied_to_the_clipboard() {
+        let data = Data::new("image/png", vec![0x89]);
+        let backends = vec![Backend::Xsel];
+        let request = Request::new(Contents::Data(data), CopyTarget::Both, backends, true);
+        let (runs, iced) = drive(request);
+        assert_eq!(runs, [(Backend::Xsel, false)]);
+        assert_eq!(iced, [Selection::Clipboard]);
+    }
+
+    #[test]
+    fn html_is_offered_with_plain_text() {
+        let data = Data::text("🦀", Some("<span>🦀</span>"));
+        let available = data.available();
+        assert_eq!(available.first().unwrap(), "text/plain;charset=utf-8");
+        for mime in ["text/plain", "UTF8_STRING", "STRING", "TEXT"] {
+            assert_eq!(data.as_bytes(mime).as_deref(), Some("🦀".as_bytes()));
+        }
+        assert_eq!(
+            data.as_bytes("text/html").as_deref(),
+            Some("<span>🦀</span>".as_bytes())
+        );
+    }
+
+    #[test]
+    fn templates_are_expanded() {
+        let (program, args) = expand_template(
+            "mycopy --to {selection} {emoji}",
+            &[(SELECTION_PLACEHOLDER, "primary"), (PLACEHOLDER, "🦀")],
+        )
+        .unwrap();
+        assert_eq!(program, "mycopy");
+        assert_eq!(args, ["--to", "primary", "🦀"]);
+        assert!(expand_template(" ", &[]).is_err());
+    }
+}
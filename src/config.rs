@@ -3,6 +3,11 @@
 use cosmic::cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry};
 
 use serde::{Deserialize, Serialize};
+
+use crate::clipboard;
+use crate::font::Unsupported;
+use crate::format::{CopyFormat, Presentation};
+use crate::pack::PackCopy;
 pub const CONFIG_VERSION: u64 = 1;
 
 #[derive(Clone, CosmicConfigEntry, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -20,9 +25,51 @@ pub struct Config {
     #[serde(default)]
     pub use_wl_copy: bool,
     #[serde(default)]
+    pub wl_copy_path: String,
+    #[serde(default)]
+    pub clipboard_backends: Vec<clipboard::Backend>,
+    #[serde(default)]
+    pub copy_target: clipboard::CopyTarget,
+    #[serde(default)]
+    pub insert_command: String,
+    #[serde(default)]
+    pub insert_on_click: bool,
+    #[serde(default)]
+    pub copy_format: CopyFormat,
+    #[serde(default)]
+    pub image_size: u16,
+    #[serde(default)]
+    pub image_dir: String,
+    #[serde(default)]
+    pub copy_html: bool,
+    #[serde(default)]
+    pub presentation: Presentation,
+    #[serde(default)]
+    pub post_copy_command: String,
+    #[serde(default)]
+    pub unicode_data_dir: String,
+    #[serde(default)]
+    pub unsupported_emojis: Unsupported,
+    #[serde(default)]
+    pub max_unicode_version: String,
+    #[serde(default)]
+    pub show_subgroups: bool,
+    #[serde(default)]
     pub close_on_copy: bool,
     #[serde(default)]
     pub show_preview: bool,
+    #[serde(default)]
+    pub collections: Vec<Collection>,
+    #[serde(default)]
+    pub snippets: Vec<Snippet>,
+    #[serde(default)]
+    pub ucd_dir: String,
+    #[serde(default)]
+    pub symbol_blocks: Vec<String>,
+    #[serde(default)]
+    pub last_used_symbols: Vec<String>,
+    #[serde(default)]
+    pub pack_copy: PackCopy,
 }
 
 impl Default for Config {
@@ -34,9 +81,71 @@ impl Default for Config {
             font_family: "Noto Color Emoji".into(),
             show_unicode: false,
             use_wl_copy: false,
+            wl_copy_path: "wl-copy".into(),
+            clipboard_backends: vec![clipboard::Backend::Iced],
+            copy_target: clipboard::CopyTarget::Clipboard,
+            insert_command: "wtype {emoji}".into(),
+            insert_on_click: false,
+            copy_format: CopyFormat::Emoji,
+            image_size: 128,
+            image_dir: String::new(),
+            copy_html: true,
+            presentation: Presentation::AsIs,
+            post_copy_command: String::new(),
+            unicode_data_dir: "/usr/share/unicode/emoji".into(),
+            unsupported_emojis: Unsupported::Show,
+            max_unicode_version: String::new(),
+            show_subgroups: true,
             close_on_copy: true,
             show_preview: true,
+            collections: Vec::new(),
+            snippets: Vec::new(),
+            ucd_dir: "/usr/share/unicode".into(),
+            symbol_blocks: [
+                "General Punctuation",
+                "Superscripts and Subscripts",
+                "Currency Symbols",
+                "Letterlike Symbols",
+                "Number Forms",
+                "Arrows",
+                "Mathematical Operators",
+                "Miscellaneous Technical",
+                "Box Drawing",
+                "Block Elements",
+                "Geometric Shapes",
+                "Miscellaneous Symbols",
+                "Dingbats",
+                "Greek and Coptic",
+            ]
+            .map(String::from)
+            .to_vec(),
+            last_used_symbols: Vec::new(),
+            pack_copy: PackCopy::Image,
+        }
+    }
+}
+
+impl Config {
+    /// The backends to try in order, `use_wl_copy` puts `wl-copy` first.
+    pub fn clipboard_backends(&self) -> Vec<clipboard::Backend> {
+        let mut backends = self.clipboard_backends.clone();
+        if self.use_wl_copy && !backends.contains(&clipboard::Backend::WlCopy) {
+            backends.insert(0, clipboard::Backend::WlCopy);
         }
+        backends
+    }
+
+    /// The backends that work without a window, for copying after the popup is closed.
+    pub fn external_clipboard_backends(&self) -> Vec<clipboard::Backend> {
+        use clipboard::Backend;
+        let mut backends = self.clipboard_backends();
+        backends.retain(|backend| *backend != Backend::Iced);
+        for backend in [Backend::WlCopy, Backend::Xclip, Backend::Xsel] {
+            if !backends.contains(&backend) {
+                backends.push(backend);
+            }
+        }
+        backends
     }
 }
 
@@ -47,3 +156,27 @@ pub struct Annotation {
     #[serde(default)]
     pub tts: Vec<String>,
 }
+
+/// A user-defined set of emojis, shown as an additional tab after the emoji groups.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Default, Clone)]
+pub struct Collection {
+    #[serde(default)]
+    pub name: String,
+    /// Either an emoji or the name of an icon.
+    #[serde(default)]
+    pub icon: String,
+    #[serde(default)]
+    pub emojis: Vec<String>,
+}
+
+/// A user-defined text, shown in the custom tab.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Default, Clone)]
+pub struct Snippet {
+    #[serde(default)]
+    pub text: String,
+    /// Defaults to the text.
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
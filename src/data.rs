@@ -0,0 +1,702 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io;
+use std::iter;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use emojis::{Group, UnicodeVersion};
+use once_cell::sync::{Lazy, OnceCell};
+
+use crate::kaomoji;
+use crate::pack;
+use crate::snippet;
+
+static EMOJIS: OnceCell<Emojis> = OnceCell::new();
+// the entries loaded at runtime, see `intern`
+static INTERNED: Lazy<Mutex<HashSet<&'static Emoji>>> = Lazy::new(Mutex::default);
+
+/// File of the Unicode emoji data with groups, ordering and names.
+pub const EMOJI_TEST: &str = "emoji-test.txt";
+/// File of the Unicode emoji data with the characters which have an emoji and a text presentation.
+pub const EMOJI_VARIATION_SEQUENCES: &str = "emoji-variation-sequences.txt";
+/// Zero width joiner, which combines emojis into a sequence like 👩‍🚀.
+pub const ZWJ: char = '\u{200d}';
+pub const SKIN_TONES: [char; 5] = [
+    '\u{1f3fb}',
+    '\u{1f3fc}',
+    '\u{1f3fd}',
+    '\u{1f3fe}',
+    '\u{1f3ff}',
+];
+/// Components which only appear after a ZWJ, e.g. in 👨‍🦰, and are not in the emoji groups.
+pub const HAIR_STYLES: [char; 4] = ['\u{1f9b0}', '\u{1f9b1}', '\u{1f9b3}', '\u{1f9b2}'];
+
+/// All emojis in display order, either loaded from the Unicode data files or built-in.
+#[derive(Debug, Default)]
+pub struct Emojis {
+    emojis: Vec<Emoji>,
+    // emojis without variation selectors to their index
+    index: HashMap<String, usize>,
+    // skin tone variants without variation selectors, which are looked up but not shown
+    skin_tones: HashMap<String, Emoji>,
+    // characters which accept a variation selector
+    variation_bases: HashSet<char>,
+}
+
+/// Where an entry comes from, everything but emojis is shown with the UI font.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Emoji,
+    Kaomoji,
+    /// A user-defined snippet
+    Custom,
+    /// A character from the Unicode character database
+    Symbol,
+    /// An image of an emoji pack
+    Image,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Emoji {
+    emoji: String,
+    name: String,
+    kind: Kind,
+    group: Option<Group>,
+    subgroup: String,
+    keywords: Vec<String>,
+    unicode_version: Option<UnicodeVersion>,
+    shortcode: Option<&'static str>,
+    image: Option<PathBuf>,
+}
+
+impl Emoji {
+    /// An entry which is not in the emoji data, like a kaomoji.
+    pub fn text(kind: Kind, text: &str, name: &str, keywords: &[&str]) -> Self {
+        Emoji {
+            emoji: text.to_string(),
+            name: name.to_string(),
+            kind,
+            group: None,
+            subgroup: String::new(),
+            keywords: keywords.iter().map(|keyword| keyword.to_string()).collect(),
+            unicode_version: None,
+            shortcode: None,
+            image: None,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.emoji
+    }
+
+    /// The CLDR name in English.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sections the entry, like the subgroups of emojis.
+    pub fn with_subgroup(mut self, subgroup: &str) -> Self {
+        self.subgroup = subgroup.to_string();
+        self
+    }
+
+    pub fn with_image(mut self, image: PathBuf) -> Self {
+        self.image = Some(image);
+        self
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Whether the entry is rendered with the UI font instead of the emoji font.
+    pub fn is_text(&self) -> bool {
+        self.kind != Kind::Emoji
+    }
+
+    /// Whether the entry needs a wider cell than a single character.
+    pub fn is_wide(&self) -> bool {
+        matches!(self.kind, Kind::Kaomoji | Kind::Custom)
+    }
+
+    pub fn group(&self) -> Option<Group> {
+        self.group
+    }
+
+    /// The Unicode subgroup, e.g. `face-smiling`, empty for entries without one.
+    /// For symbols, the Unicode block.
+    pub fn subgroup(&self) -> &str {
+        &self.subgroup
+    }
+
+    /// Additional search terms.
+    pub fn keywords(&self) -> &[String] {
+        &self.keywords
+    }
+
+    /// The version an emoji was introduced, `None` for other entries.
+    pub fn unicode_version(&self) -> Option<UnicodeVersion> {
+        self.unicode_version
+    }
+
+    pub fn shortcode(&self) -> Option<&str> {
+        self.shortcode
+    }
+
+    /// The file of an image emoji, whose text is its `:shortcode:`.
+    pub fn image(&self) -> Option<&Path> {
+        self.image.as_deref()
+    }
+}
+
+impl fmt::Display for Emoji {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.emoji)
+    }
+}
+
+// the first emoji of each subgroup in emoji-test.txt, which the `emojis` crate does not have,
+// in the order of `emojis::iter()`; written against emojis 0.6.4 (Unicode 16.0), so check it
+// with `builtin_emojis_have_subgroups` when bumping the crate
+const SUBGROUPS: &[(&str, &str)] = &[
+    ("😀", "face-smiling"),
+    ("🥰", "face-affection"),
+    ("😋", "face-tongue"),
+    ("🤗", "face-hand"),
+    ("🤐", "face-neutral-skeptical"),
+    ("😌", "face-sleepy"),
+    ("😷", "face-unwell"),
+    ("🤠", "face-hat"),
+    ("🥸", "face-glasses"),
+    ("😕", "face-concerned"),
+    ("😤", "face-negative"),
+    ("💩", "face-costume"),
+    ("😺", "cat-face"),
+    ("🙈", "monkey-face"),
+    ("💌", "heart"),
+    ("💋", "emotion"),
+    ("👋", "hand-fingers-open"),
+    ("👌", "hand-fingers-partial"),
+    ("👈", "hand-single-finger"),
+    ("👍", "hand-fingers-closed"),
+    ("👏", "hands"),
+    ("✍️", "hand-prop"),
+    ("💪", "body-parts"),
+    ("👶", "person"),
+    ("🙍", "person-gesture"),
+    ("🧑‍⚕️", "person-role"),
+    ("👼", "person-fantasy"),
+    ("💆", "person-activity"),
+    ("🤺", "person-sport"),
+    ("🧘", "person-resting"),
+    ("🧑‍🤝‍🧑", "family"),
+    ("🗣️", "person-symbol"),
+    ("🐵", "animal-mammal"),
+    ("🦃", "animal-bird"),
+    ("🐸", "animal-amphibian"),
+    ("🐊", "animal-reptile"),
+    ("🐳", "animal-marine"),
+    ("🐌", "animal-bug"),
+    ("💐", "plant-flower"),
+    ("🌱", "plant-other"),
+    ("🍇", "food-fruit"),
+    ("🥑", "food-vegetable"),
+    ("🍞", "food-prepared"),
+    ("🍱", "food-asian"),
+    ("🍦", "food-sweet"),
+    ("🍼", "drink"),
+    ("🥢", "dishware"),
+    ("🌍", "place-map"),
+    ("🏔️", "place-geographic"),
+    ("🏟️", "place-building"),
+    ("⛪", "place-religious"),
+    ("⛲", "place-other"),
+    ("🚂", "transport-ground"),
+    ("⚓", "transport-water"),
+    ("✈️", "transport-air"),
+    ("🛎️", "hotel"),
+    ("⌛", "time"),
+    ("🌑", "sky & weather"),
+    ("🎃", "event"),
+    ("🎖️", "award-medal"),
+    ("⚽", "sport"),
+    ("🎯", "game"),
+    ("🎭", "arts & crafts"),
+    ("👓", "clothing"),
+    ("🔇", "sound"),
+    ("🎼", "music"),
+    ("🎷", "musical-instrument"),
+    ("📱", "phone"),
+    ("🔋", "computer"),
+    ("🎥", "light & video"),
+    ("📔", "book-paper"),
+    ("💰", "money"),
+    ("✉️", "mail"),
+    ("✏️", "writing"),
+    ("💼", "office"),
+    ("🔒", "lock"),
+    ("🔨", "tool"),
+    ("⚗️", "science"),
+    ("💉", "medical"),
+    ("🚪", "household"),
+    ("🚬", "other-object"),
+    ("🏧", "transport-sign"),
+    ("⚠️", "warning"),
+    ("⬆️", "arrow"),
+    ("🛐", "religion"),
+    ("♈", "zodiac"),
+    ("🔀", "av-symbol"),
+    ("♀️", "gender"),
+    ("✖️", "math"),
+    ("‼️", "punctuation"),
+    ("💱", "currency"),
+    ("⚕️", "other-symbol"),
+    ("#️⃣", "keycap"),
+    ("🔠", "alphanum"),
+    ("🔴", "geometric"),
+    ("🏁", "flag"),
+    ("🇦🇨", "country-flag"),
+    ("🏴󠁧󠁢󠁥󠁮󠁧󠁿", "subdivision-flag"),
+];
+
+impl FromIterator<Emoji> for Emojis {
+    fn from_iter<T: IntoIterator<Item = Emoji>>(iter: T) -> Self {
+        let emojis: Vec<_> = iter.into_iter().collect();
+        let index: HashMap<_, _> = emojis
+            .iter()
+            .enumerate()
+            .map(|(i, emoji)| (strip_variation_selectors(&emoji.emoji), i))
+            .collect();
+        // fully-qualified emojis of characters with text presentation by default end with U+FE0F,
+        // those with emoji presentation by default like ☕ are only in emoji-variation-sequences.txt
+        let variation_bases = emojis
+            .iter()
+            .filter_map(|emoji| emoji.emoji.strip_suffix('\u{fe0f}'))
+            .filter_map(|base| {
+                let mut chars = base.chars();
+                chars.next().filter(|_| chars.next().is_none())
+            })
+            .collect();
+        Self {
+            emojis,
+            index,
+            skin_tones: HashMap::new(),
+            variation_bases,
+        }
+    }
+}
+
+impl Emojis {
+    /// The data compiled into the `emojis` crate.
+    pub fn builtin() -> Self {
+        let builtin = |emoji: &'static emojis::Emoji, subgroup: &str| Emoji {
+            emoji: emoji.as_str().to_string(),
+            name: emoji.name().to_string(),
+            kind: Kind::Emoji,
+            group: Some(emoji.group()),
+            subgroup: subgroup.to_string(),
+            keywords: Vec::new(),
+            unicode_version: Some(emoji.unicode_version()),
+            shortcode: emoji.shortcode(),
+            image: None,
+        };
+        let mut subgroups = SUBGROUPS.iter().peekable();
+        let mut subgroup = "";
+        let mut emojis: Self = emojis::iter()
+            .map(|emoji| {
+                if let Some((_, next)) = subgroups.next_if(|(first, _)| *first == emoji.as_str()) {
+                    subgroup = next;
+                }
+                builtin(emoji, subgroup)
+            })
+            .collect();
+        let skin_tones = emojis::iter()
+            .zip(&emojis.emojis)
+            .flat_map(|(emoji, base)| {
+                let tones = emoji.skin_tones().into_iter().flatten();
+                tones.map(move |tone| (tone, base.subgroup.as_str()))
+            })
+            .filter(|(emoji, _)| emoji.skin_tone() != Some(emojis::SkinTone::Default))
+            .map(|(emoji, subgroup)| {
+                (
+                    strip_variation_selectors(emoji.as_str()),
+                    builtin(emoji, subgroup),
+                )
+            })
+            .collect();
+        emojis.skin_tones = skin_tones;
+        emojis
+    }
+
+    /// Loads `emoji-test.txt` and `emoji-variation-sequences.txt` from `dir`.
+    pub fn load(dir: &Path) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(dir.join(EMOJI_TEST))?;
+        let mut emojis = parse_emoji_test(&contents);
+        if emojis.emojis.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("no emojis found in {EMOJI_TEST}"),
+            ));
+        }
+        match std::fs::read_to_string(dir.join(EMOJI_VARIATION_SEQUENCES)) {
+            Ok(contents) => emojis.variation_bases = parse_variation_sequences(&contents),
+            Err(err) => eprintln!(
+                "could not load {EMOJI_VARIATION_SEQUENCES}, using the sequences in {EMOJI_TEST}: {err}"
+            ),
+        }
+        Ok(emojis)
+    }
+}
+
+/// Loads the emoji data from `dir`, falling back to the built-in data.
+pub fn init(dir: &str) {
+    let emojis = if dir.is_empty() {
+        Emojis::builtin()
+    } else {
+        Emojis::load(Path::new(dir)).unwrap_or_else(|err| {
+            eprintln!("could not load emoji data from {dir}, using built-in data: {err}");
+            Emojis::builtin()
+        })
+    };
+    if EMOJIS.set(emojis).is_err() {
+        eprintln!("emoji data is already loaded");
+    }
+}
+
+fn all() -> &'static Emojis {
+    EMOJIS.get_or_init(Emojis::builtin)
+}
+
+pub fn iter() -> impl Iterator<Item = &'static Emoji> {
+    all().emojis.iter()
+}
+
+pub fn group_emojis(group: Group) -> impl Iterator<Item = &'static Emoji> {
+    iter().filter(move |emoji| emoji.group == Some(group))
+}
+
+/// Looks up an emoji ignoring variation selectors, including skin tone variants,
+/// or any other entry.
+pub fn get(s: &str) -> Option<&'static Emoji> {
+    let emojis = all();
+    let stripped = strip_variation_selectors(s);
+    emojis
+        .index
+        .get(&stripped)
+        .map(|&i| &emojis.emojis[i])
+        .or_else(|| emojis.skin_tones.get(&stripped))
+        .or_else(|| kaomoji::get(s))
+        .or_else(|| snippet::get(s))
+        .or_else(|| pack::get(s))
+}
+
+/// Makes an entry loaded at runtime, like a snippet, `'static` like the emoji data.
+///
+/// Each distinct entry is leaked once, so reloading unchanged entries reuses them.
+pub fn intern(entry: Emoji) -> &'static Emoji {
+    let mut interned = INTERNED.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(&entry) = interned.get(&entry) {
+        return entry;
+    }
+    let entry = &*Box::leak(Box::new(entry));
+    interned.insert(entry);
+    entry
+}
+
+/// Whether `s` is a recommended emoji, including sequences with skin tones.
+pub fn is_rgi(s: &str) -> bool {
+    let emojis = all();
+    let s = strip_variation_selectors(s);
+    emojis.index.contains_key(&s) || emojis.skin_tones.contains_key(&s)
+}
+
+/// The emojis which follow `prefix` and a ZWJ in a recommended sequence,
+/// without skin tones and variation selectors.
+pub fn zwj_next(prefix: &str) -> HashSet<String> {
+    let prefix: String = strip_variation_selectors(prefix)
+        .chars()
+        .chain(iter::once(ZWJ))
+        .collect();
+    let emojis = all();
+    emojis
+        .index
+        .keys()
+        .chain(emojis.skin_tones.keys())
+        .filter_map(|sequence| sequence.strip_prefix(&prefix))
+        .filter_map(|rest| rest.split(ZWJ).next())
+        .map(|next| next.chars().filter(|&c| !is_skin_tone(c)).collect())
+        .collect()
+}
+
+/// Puts `skin_tone` right after the first emoji of `emoji`, e.g. 👩‍🦰 to 👩🏽‍🦰,
+/// replacing its variation selector or previous skin tone.
+pub fn with_skin_tone(emoji: &str, skin_tone: char) -> String {
+    let mut chars = emoji.chars();
+    let Some(base) = chars.next() else {
+        return String::new();
+    };
+    let rest = chars
+        .as_str()
+        .trim_start_matches(|c| is_skin_tone(c) || matches!(c, '\u{fe0f}' | '\u{fe0e}'));
+    format!("{base}{skin_tone}{rest}")
+}
+
+/// Whether `c` has an emoji and a text presentation, selected with a variation selector.
+pub fn has_variation_sequences(c: char) -> bool {
+    all().variation_bases.contains(&c)
+}
+
+pub fn strip_variation_selectors(s: &str) -> String {
+    s.replace(['\u{fe0f}', '\u{fe0e}'], "")
+}
+
+fn group_from_name(name: &str) -> Option<Group> {
+    let group = match name {
+        "Smileys & Emotion" => Group::SmileysAndEmotion,
+        "People & Body" => Group::PeopleAndBody,
+        "Animals & Nature" => Group::AnimalsAndNature,
+        "Food & Drink" => Group::FoodAndDrink,
+        "Travel & Places" => Group::TravelAndPlaces,
+        "Activities" => Group::Activities,
+        "Objects" => Group::Objects,
+        "Symbols" => Group::Symbols,
+        "Flags" => Group::Flags,
+        // skin tones and hair styles are only used in sequences
+        _ => return None,
+    };
+    Some(group)
+}
+
+fn is_skin_tone(c: char) -> bool {
+    SKIN_TONES.contains(&c)
+}
+
+/// Parses lines like
+/// `1F600 ; fully-qualified # 😀 E1.0 grinning face`
+/// skin tone variants are only looked up, as the built-in data hides them too.
+fn parse_emoji_test(contents: &str) -> Emojis {
+    let mut group = None;
+    let mut subgroup = "";
+    let mut emojis = Vec::new();
+    let mut skin_tones = HashMap::new();
+    for line in contents.lines() {
+        if let Some(name) = line.strip_prefix("# group:") {
+            group = group_from_name(name.trim());
+            continue;
+        }
+        if let Some(name) = line.strip_prefix("# subgroup:") {
+            subgroup = name.trim();
+            continue;
+        }
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        let Some(group) = group else {
+            continue;
+        };
+        let Some((codepoints, rest)) = line.split_once(';') else {
+            continue;
+        };
+        let Some((status, comment)) = rest.split_once('#') else {
+            continue;
+        };
+        if status.trim() != "fully-qualified" {
+            continue;
+        }
+        let Some(emoji) = codepoints
+            .split_whitespace()
+            .map(|cp| u32::from_str_radix(cp, 16).ok().and_then(char::from_u32))
+            .collect::<Option<String>>()
+        else {
+            continue;
+        };
+        let mut comment = comment.trim().splitn(3, ' ');
+        let (Some(_), Some(version), Some(name)) = (comment.next(), comment.next(), comment.next())
+        else {
+            continue;
+        };
+        let Some(unicode_version) = version.strip_prefix('E').and_then(parse_version) else {
+            continue;
+        };
+        let shortcode = emojis::get(&emoji).and_then(|emoji| emoji.shortcode());
+        let has_skin_tone = emoji.chars().any(is_skin_tone);
+        let emoji = Emoji {
+            emoji,
+            name: name.to_string(),
+            kind: Kind::Emoji,
+            group: Some(group),
+            subgroup: subgroup.to_string(),
+            keywords: Vec::new(),
+            unicode_version: Some(unicode_version),
+            shortcode,
+            image: None,
+        };
+        if has_skin_tone {
+            skin_tones.insert(strip_variation_selectors(&emoji.emoji), emoji);
+        } else {
+            emojis.push(emoji);
+        }
+    }
+    let mut emojis: Emojis = emojis.into_iter().collect();
+    emojis.skin_tones = skin_tones;
+    emojis
+}
+
+/// Parses lines like
+/// `2615 FE0E  ; text style;  # (4.0) HOT BEVERAGE`
+/// into the characters which accept a variation selector.
+pub fn parse_variation_sequences(contents: &str) -> HashSet<char> {
+    contents
+        .lines()
+        .filter_map(|line| line.split(';').next())
+        .filter_map(|sequence| sequence.split_whitespace().next())
+        .filter_map(|base| u32::from_str_radix(base, 16).ok())
+        .filter_map(char::from_u32)
+        .collect()
+}
+
+/// Parses versions like `15.1` or `15`.
+pub fn parse_version(version: &str) -> Option<UnicodeVersion> {
+    let (major, minor) = version.split_once('.').unwrap_or((version, "0"));
+    Some(UnicodeVersion::new(
+        major.parse().ok()?,
+        minor.parse().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_emojis_have_subgroups() {
+        let emojis = Emojis::builtin();
+        // every subgroup is found and its emojis are consecutive
+        let mut subgroups: Vec<&str> = Vec::new();
+        for emoji in &emojis.emojis {
+            if subgroups.last() != Some(&emoji.subgroup()) {
+                subgroups.push(emoji.subgroup());
+            }
+        }
+        let expected: Vec<_> = SUBGROUPS.iter().map(|(_, subgroup)| *subgroup).collect();
+        assert_eq!(subgroups, expected);
+        let waving = &emojis.skin_tones["👋🏽"];
+        assert_eq!(waving.subgroup(), "hand-fingers-open");
+    }
+
+    #[test]
+    fn emoji_test_is_parsed() {
+        let emojis = parse_emoji_test(
+            "\
+# group: Smileys & Emotion
+
+# subgroup: face-smiling
+1F600                                                  ; fully-qualified     # 😀 E1.0 grinning face
+263A FE0F                                              ; fully-qualified     # ☺️ E0.6 smiling face
+263A                                                   ; unqualified         # ☺ E0.6 smiling face
+
+# group: People & Body
+
+# subgroup: hand-fingers-open
+1F44B                                                  ; fully-qualified     # 👋 E0.6 waving hand
+1F44B 1F3FD                                            ; fully-qualified     # 👋🏽 E1.0 waving hand: medium skin tone
+
+# group: Component
+
+# subgroup: hair-style
+1F9B0                                                  ; component           # 🦰 E11.0 red hair
+",
+        );
+        let parsed: Vec<_> = emojis
+            .emojis
+            .iter()
+            .map(|emoji| {
+                (
+                    emoji.as_str(),
+                    emoji.name(),
+                    emoji.group(),
+                    emoji.subgroup(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                (
+                    "😀",
+                    "grinning face",
+                    Some(Group::SmileysAndEmotion),
+                    "face-smiling"
+                ),
+                (
+                    "☺️",
+                    "smiling face",
+                    Some(Group::SmileysAndEmotion),
+                    "face-smiling"
+                ),
+                (
+                    "👋",
+                    "waving hand",
+                    Some(Group::PeopleAndBody),
+                    "hand-fingers-open"
+                ),
+            ]
+        );
+        assert_eq!(emojis.emojis[0].shortcode(), Some("grinning"));
+        assert_eq!(
+            emojis.emojis[1].unicode_version(),
+            Some(UnicodeVersion::new(0, 6))
+        );
+        assert_eq!(emojis.index["☺"], 1);
+        assert!(emojis.variation_bases.contains(&'☺'));
+        let waving = &emojis.skin_tones["👋🏽"];
+        assert_eq!(waving.name(), "waving hand: medium skin tone");
+        assert_eq!(waving.unicode_version(), Some(UnicodeVersion::new(1, 0)));
+    }
+
+    #[test]
+    fn recommended_sequences_are_known() {
+        assert!(is_rgi("👩‍🚀"));
+        assert!(is_rgi("👩🏽‍🚀"));
+        assert!(is_rgi("❤️‍🔥"));
+        assert!(is_rgi("❤‍🔥"));
+        assert!(!is_rgi("👩‍🦰🏽"));
+        assert!(!is_rgi("😀😀"));
+    }
+
+    #[test]
+    fn zwj_sequences_are_continued() {
+        let next = zwj_next("👩");
+        for emoji in ["🚀", "🦰", "❤", "👩"] {
+            assert!(next.contains(emoji), "{emoji}");
+        }
+        assert!(!next.contains("😀"));
+        assert!(zwj_next("👩🏽").contains("🚀"));
+        // skin tones of the following emoji are left to the skin tone buttons
+        assert!(zwj_next("🧑🏻").contains("🤝"));
+        assert!(zwj_next("🧑🏻‍🤝").contains("🧑"));
+        assert!(zwj_next("😀").is_empty());
+    }
+
+    #[test]
+    fn skin_tones_follow_the_first_emoji() {
+        assert_eq!(with_skin_tone("👋", '🏽'), "👋🏽");
+        assert_eq!(with_skin_tone("👋🏻", '🏽'), "👋🏽");
+        assert_eq!(with_skin_tone("☝️", '🏽'), "☝🏽");
+        assert_eq!(with_skin_tone("👩‍🦰", '🏽'), "👩🏽‍🦰");
+        assert_eq!(with_skin_tone("🧑🏻‍🤝‍🧑🏼", '🏿'), "🧑🏿‍🤝‍🧑🏼");
+        assert!(is_rgi(&with_skin_tone("👩‍🦰", '🏽')));
+    }
+
+    #[test]
+    fn entries_are_interned_once() {
+        let entry = || Emoji::text(Kind::Custom, "¯\\_(ツ)_/¯", "shrug", &[]);
+        let first = intern(entry());
+        assert!(std::ptr::eq(first, intern(entry())));
+        assert!(!std::ptr::eq(first, intern(entry().with_subgroup("other"))));
+    }
+}
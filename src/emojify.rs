@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use std::collections::HashMap;
+
+use crate::config::Annotation;
+use crate::data::{self, Emoji};
+
+// shorter words like `a`, `it` or `to` match too many unrelated emojis
+const MIN_WORD_LEN: usize = 3;
+
+/// The emoji for each lowercase keyword.
+#[derive(Debug, Default)]
+pub struct Index {
+    keywords: HashMap<String, &'static Emoji>,
+}
+
+impl Index {
+    /// Indexes the keywords of the locale's annotations, falling back to the English emoji names.
+    /// An emoji whose name is the keyword wins over other emojis with that keyword,
+    /// otherwise the first emoji in display order.
+    pub fn new(annotations: &HashMap<String, Annotation>) -> Self {
+        let mut names = HashMap::new();
+        let mut keywords = HashMap::new();
+        for emoji in data::iter() {
+            let key = data::strip_variation_selectors(emoji.as_str());
+            let Some(annotation) = annotations.get(&key) else {
+                continue;
+            };
+            for name in &annotation.tts {
+                names.entry(name.to_lowercase()).or_insert(emoji);
+            }
+            for keyword in &annotation.default {
+                keywords.entry(keyword.to_lowercase()).or_insert(emoji);
+            }
+        }
+        for emoji in data::iter() {
+            names.entry(emoji.name().to_lowercase()).or_insert(emoji);
+        }
+        keywords.extend(names);
+        keywords.retain(|keyword, _| !keyword.contains(char::is_whitespace));
+        Self { keywords }
+    }
+
+    /// Looks up a word, also without a plural `s`.
+    pub fn get(&self, word: &str) -> Option<&'static Emoji> {
+        if word.chars().count() < MIN_WORD_LEN {
+            return None;
+        }
+        let word = word.to_lowercase();
+        self.keywords.get(&word).copied().or_else(|| {
+            let singular = word.strip_suffix('s')?;
+            self.keywords.get(singular).copied()
+        })
+    }
+}
+
+/// A word or the text between words, with the suggested emoji for a word.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub text: String,
+    pub suggestion: Option<&'static Emoji>,
+    /// Whether the suggestion is part of the result.
+    pub accepted: bool,
+}
+
+/// Splits `text` into words and suggests an emoji for each known word.
+pub fn suggest(index: &Index, text: &str) -> Vec<Segment> {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '\'' || c == '-';
+    let mut segments = Vec::new();
+    let mut push = |part: &str, is_word: bool| {
+        let suggestion = is_word.then(|| index.get(part)).flatten();
+        segments.push(Segment {
+            text: part.to_string(),
+            suggestion,
+            accepted: suggestion.is_some(),
+        });
+    };
+    let mut start = 0;
+    let mut in_word = false;
+    for (i, c) in text.char_indices() {
+        if i == start {
+            in_word = is_word_char(c);
+        } else if is_word_char(c) != in_word {
+            push(&text[start..i], in_word);
+            start = i;
+            in_word = !in_word;
+        }
+    }
+    if start < text.len() {
+        push(&text[start..], in_word);
+    }
+    segments
+}
+
+/// The text with the accepted emojis after their words, e.g. `ship 🚢 it`.
+pub fn render(segments: &[Segment]) -> String {
+    let mut rendered = String::new();
+    for segment in segments {
+        rendered.push_str(&segment.text);
+        if let Some(emoji) = segment.suggestion.filter(|_| segment.accepted) {
+            rendered.push(' ');
+            rendered.push_str(emoji.as_str());
+        }
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotation(tts: &[&str], default: &[&str]) -> Annotation {
+        Annotation {
+            default: default.iter().map(|s| s.to_string()).collect(),
+            tts: tts.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn keywords_are_chosen_in_display_order() {
+        let annotations = HashMap::from([
+            ("🔥".to_string(), annotation(&["fire"], &["hot", "flame"])),
+            (
+                "🥵".to_string(),
+                annotation(&["hot face"], &["hot", "sweat"]),
+            ),
+            ("⛴".to_string(), annotation(&["ferry"], &["boat", "ship"])),
+            ("🚢".to_string(), annotation(&["ship"], &["boat"])),
+        ]);
+        let index = Index::new(&annotations);
+        let get = |word| index.get(word).map(Emoji::as_str);
+        // 🥵 comes before 🔥
+        assert_eq!(get("hot"), Some("🥵"));
+        assert_eq!(get("flame"), Some("🔥"));
+        // ⛴️ comes before 🚢, but is not named ship
+        assert_eq!(get("ship"), Some("🚢"));
+        assert_eq!(get("Ships"), Some("🚢"));
+        // English names are used without annotations, short words are skipped
+        assert_eq!(get("crab"), Some("🦀"));
+        assert_eq!(get("ox"), None);
+    }
+
+    #[test]
+    fn suggestions_follow_their_words() {
+        let index = Index::new(&HashMap::new());
+        let mut segments = suggest(&index, "ship it, crabs!");
+        let texts: Vec<_> = segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, ["ship", " ", "it", ", ", "crabs", "!"]);
+        let suggestions: Vec<_> = segments
+            .iter()
+            .map(|s| s.suggestion.map(Emoji::as_str))
+            .collect();
+        assert_eq!(
+            suggestions,
+            [Some("🚢"), None, None, None, Some("🦀"), None]
+        );
+        assert_eq!(render(&segments), "ship 🚢 it, crabs 🦀!");
+
+        segments[4].accepted = false;
+        assert_eq!(render(&segments), "ship 🚢 it, crabs!");
+    }
+}
@@ -0,0 +1,422 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::fl;
+
+const REGIONAL_INDICATOR_A: u32 = 0x1f1e6;
+const BLACK_FLAG: char = '\u{1f3f4}';
+// tags are the ASCII characters offset by this
+const TAG_BASE: u32 = 0xe0000;
+const CANCEL_TAG: char = '\u{e007f}';
+
+/// Continents as in the UN M49 standard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    Africa,
+    Americas,
+    Antarctica,
+    Asia,
+    Europe,
+    Oceania,
+}
+
+impl Region {
+    pub fn name(self) -> String {
+        match self {
+            Region::Africa => fl!("region-africa"),
+            Region::Americas => fl!("region-americas"),
+            Region::Antarctica => fl!("region-antarctica"),
+            Region::Asia => fl!("region-asia"),
+            Region::Europe => fl!("region-europe"),
+            Region::Oceania => fl!("region-oceania"),
+        }
+    }
+}
+
+/// The kinds of flags in the order of the Unicode data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    /// Like 🏁 or 🏳️‍🌈
+    Other,
+    /// A pair of regional indicators like 🇩🇪
+    Country,
+    /// A tag sequence like 🏴󠁧󠁢󠁳󠁣󠁴󠁿
+    Subdivision,
+}
+
+// ISO 3166-1 alpha-2 and alpha-3 codes, the flags of some territories have no alpha-3 code
+const COUNTRIES: &[(&str, &str, Option<Region>)] = &[
+    ("AC", "", Some(Region::Africa)),
+    ("AD", "AND", Some(Region::Europe)),
+    ("AE", "ARE", Some(Region::Asia)),
+    ("AF", "AFG", Some(Region::Asia)),
+    ("AG", "ATG", Some(Region::Americas)),
+    ("AI", "AIA", Some(Region::Americas)),
+    ("AL", "ALB", Some(Region::Europe)),
+    ("AM", "ARM", Some(Region::Asia)),
+    ("AO", "AGO", Some(Region::Africa)),
+    ("AQ", "ATA", Some(Region::Antarctica)),
+    ("AR", "ARG", Some(Region::Americas)),
+    ("AS", "ASM", Some(Region::Oceania)),
+    ("AT", "AUT", Some(Region::Europe)),
+    ("AU", "AUS", Some(Region::Oceania)),
+    ("AW", "ABW", Some(Region::Americas)),
+    ("AX", "ALA", Some(Region::Europe)),
+    ("AZ", "AZE", Some(Region::Asia)),
+    ("BA", "BIH", Some(Region::Europe)),
+    ("BB", "BRB", Some(Region::Americas)),
+    ("BD", "BGD", Some(Region::Asia)),
+    ("BE", "BEL", Some(Region::Europe)),
+    ("BF", "BFA", Some(Region::Africa)),
+    ("BG", "BGR", Some(Region::Europe)),
+    ("BH", "BHR", Some(Region::Asia)),
+    ("BI", "BDI", Some(Region::Africa)),
+    ("BJ", "BEN", Some(Region::Africa)),
+    ("BL", "BLM", Some(Region::Americas)),
+    ("BM", "BMU", Some(Region::Americas)),
+    ("BN", "BRN", Some(Region::Asia)),
+    ("BO", "BOL", Some(Region::Americas)),
+    ("BQ", "BES", Some(Region::Americas)),
+    ("BR", "BRA", Some(Region::Americas)),
+    ("BS", "BHS", Some(Region::Americas)),
+    ("BT", "BTN", Some(Region::Asia)),
+    ("BV", "BVT", Some(Region::Americas)),
+    ("BW", "BWA", Some(Region::Africa)),
+    ("BY", "BLR", Some(Region::Europe)),
+    ("BZ", "BLZ", Some(Region::Americas)),
+    ("CA", "CAN", Some(Region::Americas)),
+    ("CC", "CCK", Some(Region::Oceania)),
+    ("CD", "COD", Some(Region::Africa)),
+    ("CF", "CAF", Some(Region::Africa)),
+    ("CG", "COG", Some(Region::Africa)),
+    ("CH", "CHE", Some(Region::Europe)),
+    ("CI", "CIV", Some(Region::Africa)),
+    ("CK", "COK", Some(Region::Oceania)),
+    ("CL", "CHL", Some(Region::Americas)),
+    ("CM", "CMR", Some(Region::Africa)),
+    ("CN", "CHN", Some(Region::Asia)),
+    ("CO", "COL", Some(Region::Americas)),
+    ("CP", "", Some(Region::Americas)),
+    ("CQ", "", Some(Region::Europe)),
+    ("CR", "CRI", Some(Region::Americas)),
+    ("CU", "CUB", Some(Region::Americas)),
+    ("CV", "CPV", Some(Region::Africa)),
+    ("CW", "CUW", Some(Region::Americas)),
+    ("CX", "CXR", Some(Region::Oceania)),
+    ("CY", "CYP", Some(Region::Asia)),
+    ("CZ", "CZE", Some(Region::Europe)),
+    ("DE", "DEU", Some(Region::Europe)),
+    ("DG", "", Some(Region::Africa)),
+    ("DJ", "DJI", Some(Region::Africa)),
+    ("DK", "DNK", Some(Region::Europe)),
+    ("DM", "DMA", Some(Region::Americas)),
+    ("DO", "DOM", Some(Region::Americas)),
+    ("DZ", "DZA", Some(Region::Africa)),
+    ("EA", "", Some(Region::Africa)),
+    ("EC", "ECU", Some(Region::Americas)),
+    ("EE", "EST", Some(Region::Europe)),
+    ("EG", "EGY", Some(Region::Africa)),
+    ("EH", "ESH", Some(Region::Africa)),
+    ("ER", "ERI", Some(Region::Africa)),
+    ("ES", "ESP", Some(Region::Europe)),
+    ("ET", "ETH", Some(Region::Africa)),
+    ("EU", "", Some(Region::Europe)),
+    ("FI", "FIN", Some(Region::Europe)),
+    ("FJ", "FJI", Some(Region::Oceania)),
+    ("FK", "FLK", Some(Region::Americas)),
+    ("FM", "FSM", Some(Region::Oceania)),
+    ("FO", "FRO", Some(Region::Europe)),
+    ("FR", "FRA", Some(Region::Europe)),
+    ("GA", "GAB", Some(Region::Africa)),
+    ("GB", "GBR", Some(Region::Europe)),
+    ("GD", "GRD", Some(Region::Americas)),
+    ("GE", "GEO", Some(Region::Asia)),
+    ("GF", "GUF", Some(Region::Americas)),
+    ("GG", "GGY", Some(Region::Europe)),
+    ("GH", "GHA", Some(Region::Africa)),
+    ("GI", "GIB", Some(Region::Europe)),
+    ("GL", "GRL", Some(Region::Americas)),
+    ("GM", "GMB", Some(Region::Africa)),
+    ("GN", "GIN", Some(Region::Africa)),
+    ("GP", "GLP", Some(Region::Americas)),
+    ("GQ", "GNQ", Some(Region::Africa)),
+    ("GR", "GRC", Some(Region::Europe)),
+    ("GS", "SGS", Some(Region::Americas)),
+    ("GT", "GTM", Some(Region::Americas)),
+    ("GU", "GUM", Some(Region::Oceania)),
+    ("GW", "GNB", Some(Region::Africa)),
+    ("GY", "GUY", Some(Region::Americas)),
+    ("HK", "HKG", Some(Region::Asia)),
+    ("HM", "HMD", Some(Region::Oceania)),
+    ("HN", "HND", Some(Region::Americas)),
+    ("HR", "HRV", Some(Region::Europe)),
+    ("HT", "HTI", Some(Region::Americas)),
+    ("HU", "HUN", Some(Region::Europe)),
+    ("IC", "", Some(Region::Africa)),
+    ("ID", "IDN", Some(Region::Asia)),
+    ("IE", "IRL", Some(Region::Europe)),
+    ("IL", "ISR", Some(Region::Asia)),
+    ("IM", "IMN", Some(Region::Europe)),
+    ("IN", "IND", Some(Region::Asia)),
+    ("IO", "IOT", Some(Region::Africa)),
+    ("IQ", "IRQ", Some(Region::Asia)),
+    ("IR", "IRN", Some(Region::Asia)),
+    ("IS", "ISL", Some(Region::Europe)),
+    ("IT", "ITA", Some(Region::Europe)),
+    ("JE", "JEY", Some(Region::Europe)),
+    ("JM", "JAM", Some(Region::Americas)),
+    ("JO", "JOR", Some(Region::Asia)),
+    ("JP", "JPN", Some(Region::Asia)),
+    ("KE", "KEN", Some(Region::Africa)),
+    ("KG", "KGZ", Some(Region::Asia)),
+    ("KH", "KHM", Some(Region::Asia)),
+    ("KI", "KIR", Some(Region::Oceania)),
+    ("KM", "COM", Some(Region::Africa)),
+    ("KN", "KNA", Some(Region::Americas)),
+    ("KP", "PRK", Some(Region::Asia)),
+    ("KR", "KOR", Some(Region::Asia)),
+    ("KW", "KWT", Some(Region::Asia)),
+    ("KY", "CYM", Some(Region::Americas)),
+    ("KZ", "KAZ", Some(Region::Asia)),
+    ("LA", "LAO", Some(Region::Asia)),
+    ("LB", "LBN", Some(Region::Asia)),
+    ("LC", "LCA", Some(Region::Americas)),
+    ("LI", "LIE", Some(Region::Europe)),
+    ("LK", "LKA", Some(Region::Asia)),
+    ("LR", "LBR", Some(Region::Africa)),
+    ("LS", "LSO", Some(Region::Africa)),
+    ("LT", "LTU", Some(Region::Europe)),
+    ("LU", "LUX", Some(Region::Europe)),
+    ("LV", "LVA", Some(Region::Europe)),
+    ("LY", "LBY", Some(Region::Africa)),
+    ("MA", "MAR", Some(Region::Africa)),
+    ("MC", "MCO", Some(Region::Europe)),
+    ("MD", "MDA", Some(Region::Europe)),
+    ("ME", "MNE", Some(Region::Europe)),
+    ("MF", "MAF", Some(Region::Americas)),
+    ("MG", "MDG", Some(Region::Africa)),
+    ("MH", "MHL", Some(Region::Oceania)),
+    ("MK", "MKD", Some(Region::Europe)),
+    ("ML", "MLI", Some(Region::Africa)),
+    ("MM", "MMR", Some(Region::Asia)),
+    ("MN", "MNG", Some(Region::Asia)),
+    ("MO", "MAC", Some(Region::Asia)),
+    ("MP", "MNP", Some(Region::Oceania)),
+    ("MQ", "MTQ", Some(Region::Americas)),
+    ("MR", "MRT", Some(Region::Africa)),
+    ("MS", "MSR", Some(Region::Americas)),
+    ("MT", "MLT", Some(Region::Europe)),
+    ("MU", "MUS", Some(Region::Africa)),
+    ("MV", "MDV", Some(Region::Asia)),
+    ("MW", "MWI", Some(Region::Africa)),
+    ("MX", "MEX", Some(Region::Americas)),
+    ("MY", "MYS", Some(Region::Asia)),
+    ("MZ", "MOZ", Some(Region::Africa)),
+    ("NA", "NAM", Some(Region::Africa)),
+    ("NC", "NCL", Some(Region::Oceania)),
+    ("NE", "NER", Some(Region::Africa)),
+    ("NF", "NFK", Some(Region::Oceania)),
+    ("NG", "NGA", Some(Region::Africa)),
+    ("NI", "NIC", Some(Region::Americas)),
+    ("NL", "NLD", Some(Region::Europe)),
+    ("NO", "NOR", Some(Region::Europe)),
+    ("NP", "NPL", Some(Region::Asia)),
+    ("NR", "NRU", Some(Region::Oceania)),
+    ("NU", "NIU", Some(Region::Oceania)),
+    ("NZ", "NZL", Some(Region::Oceania)),
+    ("OM", "OMN", Some(Region::Asia)),
+    ("PA", "PAN", Some(Region::Americas)),
+    ("PE", "PER", Some(Region::Americas)),
+    ("PF", "PYF", Some(Region::Oceania)),
+    ("PG", "PNG", Some(Region::Oceania)),
+    ("PH", "PHL", Some(Region::Asia)),
+    ("PK", "PAK", Some(Region::Asia)),
+    ("PL", "POL", Some(Region::Europe)),
+    ("PM", "SPM", Some(Region::Americas)),
+    ("PN", "PCN", Some(Region::Oceania)),
+    ("PR", "PRI", Some(Region::Americas)),
+    ("PS", "PSE", Some(Region::Asia)),
+    ("PT", "PRT", Some(Region::Europe)),
+    ("PW", "PLW", Some(Region::Oceania)),
+    ("PY", "PRY", Some(Region::Americas)),
+    ("QA", "QAT", Some(Region::Asia)),
+    ("RE", "REU", Some(Region::Africa)),
+    ("RO", "ROU", Some(Region::Europe)),
+    ("RS", "SRB", Some(Region::Europe)),
+    ("RU", "RUS", Some(Region::Europe)),
+    ("RW", "RWA", Some(Region::Africa)),
+    ("SA", "SAU", Some(Region::Asia)),
+    ("SB", "SLB", Some(Region::Oceania)),
+    ("SC", "SYC", Some(Region::Africa)),
+    ("SD", "SDN", Some(Region::Africa)),
+    ("SE", "SWE", Some(Region::Europe)),
+    ("SG", "SGP", Some(Region::Asia)),
+    ("SH", "SHN", Some(Region::Africa)),
+    ("SI", "SVN", Some(Region::Europe)),
+    ("SJ", "SJM", Some(Region::Europe)),
+    ("SK", "SVK", Some(Region::Europe)),
+    ("SL", "SLE", Some(Region::Africa)),
+    ("SM", "SMR", Some(Region::Europe)),
+    ("SN", "SEN", Some(Region::Africa)),
+    ("SO", "SOM", Some(Region::Africa)),
+    ("SR", "SUR", Some(Region::Americas)),
+    ("SS", "SSD", Some(Region::Africa)),
+    ("ST", "STP", Some(Region::Africa)),
+    ("SV", "SLV", Some(Region::Americas)),
+    ("SX", "SXM", Some(Region::Americas)),
+    ("SY", "SYR", Some(Region::Asia)),
+    ("SZ", "SWZ", Some(Region::Africa)),
+    ("TA", "", Some(Region::Africa)),
+    ("TC", "TCA", Some(Region::Americas)),
+    ("TD", "TCD", Some(Region::Africa)),
+    ("TF", "ATF", Some(Region::Africa)),
+    ("TG", "TGO", Some(Region::Africa)),
+    ("TH", "THA", Some(Region::Asia)),
+    ("TJ", "TJK", Some(Region::Asia)),
+    ("TK", "TKL", Some(Region::Oceania)),
+    ("TL", "TLS", Some(Region::Asia)),
+    ("TM", "TKM", Some(Region::Asia)),
+    ("TN", "TUN", Some(Region::Africa)),
+    ("TO", "TON", Some(Region::Oceania)),
+    ("TR", "TUR", Some(Region::Asia)),
+    ("TT", "TTO", Some(Region::Americas)),
+    ("TV", "TUV", Some(Region::Oceania)),
+    ("TW", "TWN", Some(Region::Asia)),
+    ("TZ", "TZA", Some(Region::Africa)),
+    ("UA", "UKR", Some(Region::Europe)),
+    ("UG", "UGA", Some(Region::Africa)),
+    ("UM", "UMI", Some(Region::Oceania)),
+    ("UN", "", None),
+    ("US", "USA", Some(Region::Americas)),
+    ("UY", "URY", Some(Region::Americas)),
+    ("UZ", "UZB", Some(Region::Asia)),
+    ("VA", "VAT", Some(Region::Europe)),
+    ("VC", "VCT", Some(Region::Americas)),
+    ("VE", "VEN", Some(Region::Americas)),
+    ("VG", "VGB", Some(Region::Americas)),
+    ("VI", "VIR", Some(Region::Americas)),
+    ("VN", "VNM", Some(Region::Asia)),
+    ("VU", "VUT", Some(Region::Oceania)),
+    ("WF", "WLF", Some(Region::Oceania)),
+    ("WS", "WSM", Some(Region::Oceania)),
+    ("XK", "XKX", Some(Region::Europe)),
+    ("YE", "YEM", Some(Region::Asia)),
+    ("YT", "MYT", Some(Region::Africa)),
+    ("ZA", "ZAF", Some(Region::Africa)),
+    ("ZM", "ZMB", Some(Region::Africa)),
+    ("ZW", "ZWE", Some(Region::Africa)),
+];
+
+/// Compares names without case and diacritics, so that Österreich is sorted with the Os.
+pub fn sort_key(name: &str) -> String {
+    name.nfd()
+        .filter(|&c| !is_combining_mark(c))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+pub fn kind(emoji: &str) -> Kind {
+    if country_code(emoji).is_some() {
+        Kind::Country
+    } else if subdivision_code(emoji).is_some() {
+        Kind::Subdivision
+    } else {
+        Kind::Other
+    }
+}
+
+/// The alpha-2 code of country flags, e.g. `DE` for 🇩🇪.
+pub fn country_code(emoji: &str) -> Option<String> {
+    let code = emoji
+        .chars()
+        .map(|c| {
+            let offset = (c as u32).checked_sub(REGIONAL_INDICATOR_A)?;
+            (offset < 26).then(|| char::from(b'A' + offset as u8))
+        })
+        .collect::<Option<String>>()?;
+    (code.len() == 2).then_some(code)
+}
+
+/// The ISO 3166-2 code of subdivision flags, e.g. `GB-SCT` for 🏴󠁧󠁢󠁳󠁣󠁴󠁿.
+pub fn subdivision_code(emoji: &str) -> Option<String> {
+    let tags = emoji.strip_prefix(BLACK_FLAG)?.strip_suffix(CANCEL_TAG)?;
+    let tags = tags
+        .chars()
+        .map(|c| {
+            let tag = char::from_u32((c as u32).checked_sub(TAG_BASE)?)?;
+            tag.is_ascii_alphanumeric().then_some(tag)
+        })
+        .collect::<Option<String>>()?;
+    if tags.len() < 3 {
+        return None;
+    }
+    let (country, subdivision) = tags.split_at(2);
+    Some(format!("{country}-{subdivision}").to_ascii_uppercase())
+}
+
+/// The region of country and subdivision flags.
+pub fn region(emoji: &str) -> Option<Region> {
+    let code = country_code(emoji).or_else(|| {
+        let code = subdivision_code(emoji)?;
+        Some(code.get(..2)?.to_string())
+    })?;
+    let (_, _, region) = COUNTRIES.iter().find(|(alpha2, _, _)| *alpha2 == code)?;
+    *region
+}
+
+/// Whether `query` is the alpha-2 or alpha-3 code of a country flag,
+/// or the code of a subdivision flag with or without the country, e.g. `gb-sct` or `sct`.
+pub fn matches_code(emoji: &str, query: &str) -> bool {
+    let query = query.trim();
+    if let Some(code) = country_code(emoji) {
+        let alpha3 = COUNTRIES
+            .iter()
+            .find(|(alpha2, _, _)| *alpha2 == code)
+            .map_or("", |(_, alpha3, _)| alpha3);
+        return code.eq_ignore_ascii_case(query)
+            || !alpha3.is_empty() && alpha3.eq_ignore_ascii_case(query);
+    }
+    if let Some(code) = subdivision_code(emoji) {
+        let (country, subdivision) = code.split_at(2);
+        return code.eq_ignore_ascii_case(query)
+            || subdivision[1..].eq_ignore_ascii_case(query)
+            || format!("{country}{}", &subdivision[1..]).eq_ignore_ascii_case(query);
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GERMANY: &str = "🇩🇪";
+    const SCOTLAND: &str = "\u{1f3f4}\u{e0067}\u{e0062}\u{e0073}\u{e0063}\u{e0074}\u{e007f}";
+
+    #[test]
+    fn subdivision_codes_are_read_from_tags() {
+        assert_eq!(subdivision_code(SCOTLAND).as_deref(), Some("GB-SCT"));
+        assert_eq!(subdivision_code(GERMANY), None);
+        // a black flag without tags, and tags without the cancel tag
+        assert_eq!(subdivision_code("🏴"), None);
+        assert_eq!(subdivision_code(&SCOTLAND[..SCOTLAND.len() - 4]), None);
+        assert_eq!(region(SCOTLAND), Some(Region::Europe));
+    }
+
+    #[test]
+    fn codes_are_matched() {
+        for query in ["de", "DE", "deu", " Deu "] {
+            assert!(matches_code(GERMANY, query), "{query}");
+        }
+        for query in ["gb-sct", "GB-SCT", "sct", "gbsct"] {
+            assert!(matches_code(SCOTLAND, query), "{query}");
+        }
+        for query in ["d", "ger", "gb", "gb-", "sc"] {
+            assert!(!matches_code(GERMANY, query), "{query}");
+            assert!(!matches_code(SCOTLAND, query), "{query}");
+        }
+        assert!(!matches_code("😀", "de"));
+    }
+}
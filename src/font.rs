@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use std::collections::HashSet;
+use std::io;
+use std::sync::Mutex;
+
+use cosmic_text::fontdb::{Family, Query};
+use cosmic_text::{rustybuzz, FontSystem};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::data;
+
+// loading the system fonts is slow, so it is only done once
+pub static FONT_SYSTEM: Lazy<Mutex<FontSystem>> = Lazy::new(|| Mutex::new(FontSystem::new()));
+
+/// How emojis are shown, which the emoji font cannot render.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Default, Clone, Copy)]
+pub enum Unsupported {
+    #[default]
+    Show,
+    Dim,
+    Hide,
+}
+
+/// The emojis which `font_family` does not render as a single glyph.
+pub fn unsupported(font_family: &str) -> io::Result<HashSet<String>> {
+    let font_system = FONT_SYSTEM
+        .lock()
+        .map_err(|_| io::Error::other("font system is poisoned"))?;
+    let db = font_system.db();
+    let families = [Family::Name(font_family)];
+    let query = Query {
+        families: &families,
+        ..Query::default()
+    };
+    let Some(id) = db.query(&query) else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("font {font_family} not found"),
+        ));
+    };
+    db.with_face_data(id, |font_data, index| {
+        let face = rustybuzz::Face::from_slice(font_data, index)?;
+        let unsupported = data::iter()
+            .filter(|emoji| !is_single_glyph(&face, emoji.as_str()))
+            .map(|emoji| emoji.as_str().to_string())
+            .collect();
+        Some(unsupported)
+    })
+    .flatten()
+    .ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("font {font_family} could not be parsed"),
+        )
+    })
+}
+
+// ZWJ sequences and flags are ligatures, otherwise the components are shown side by side
+fn is_single_glyph(face: &rustybuzz::Face, emoji: &str) -> bool {
+    let mut buffer = rustybuzz::UnicodeBuffer::new();
+    // variation selectors are kept as invisible glyphs
+    buffer.push_str(&data::strip_variation_selectors(emoji));
+    let glyphs = rustybuzz::shape(face, &[], buffer);
+    if glyphs.glyph_infos().iter().any(|info| info.glyph_id == 0) {
+        return false;
+    }
+    glyphs
+        .glyph_positions()
+        .iter()
+        .filter(|pos| pos.x_advance > 0)
+        .count()
+        == 1
+}
@@ -0,0 +1,258 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data;
+use crate::fl;
+
+/// The representation of an emoji that is copied.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Default, Clone, Copy)]
+pub enum CopyFormat {
+    /// 😀
+    #[default]
+    Emoji,
+    /// :grinning:
+    Shortcode,
+    /// U+1F600
+    Codepoint,
+    /// &#x1F600;
+    HtmlEntity,
+    /// \u{1F600}
+    Escape,
+    /// %F0%9F%98%80
+    UrlEncoded,
+}
+
+impl CopyFormat {
+    pub const ALL: [CopyFormat; 6] = [
+        CopyFormat::Emoji,
+        CopyFormat::Shortcode,
+        CopyFormat::Codepoint,
+        CopyFormat::HtmlEntity,
+        CopyFormat::Escape,
+        CopyFormat::UrlEncoded,
+    ];
+
+    /// Formats the emoji, emojis without a shortcode are returned as is.
+    /// The presentation is not applied to text entries like kaomojis.
+    pub fn format(self, emoji: &data::Emoji, presentation: Presentation) -> String {
+        let presentation = if emoji.is_text() {
+            Presentation::AsIs
+        } else {
+            presentation
+        };
+        let emoji_str = &presentation.apply(emoji.as_str());
+        match self {
+            CopyFormat::Emoji => emoji_str.to_string(),
+            CopyFormat::Shortcode => match emoji.shortcode() {
+                Some(shortcode) => format!(":{shortcode}:"),
+                None => emoji_str.to_string(),
+            },
+            CopyFormat::Codepoint => codepoints(emoji_str),
+            CopyFormat::HtmlEntity => emoji_str.chars().fold(String::new(), |mut s, c| {
+                _ = write!(s, "&#x{:X};", c as u32);
+                s
+            }),
+            CopyFormat::Escape => emoji_str.chars().fold(String::new(), |mut s, c| {
+                _ = write!(s, "\\u{{{:X}}}", c as u32);
+                s
+            }),
+            CopyFormat::UrlEncoded => emoji_str.bytes().fold(String::new(), |mut s, b| {
+                if b.is_ascii_alphanumeric() || b"-_.~".contains(&b) {
+                    s.push(b as char);
+                } else {
+                    _ = write!(s, "%{b:02X}");
+                }
+                s
+            }),
+        }
+    }
+
+    pub fn name(self) -> String {
+        match self {
+            CopyFormat::Emoji => fl!("format-emoji"),
+            CopyFormat::Shortcode => fl!("format-shortcode"),
+            CopyFormat::Codepoint => fl!("format-codepoint"),
+            CopyFormat::HtmlEntity => fl!("format-html-entity"),
+            CopyFormat::Escape => fl!("format-escape"),
+            CopyFormat::UrlEncoded => fl!("format-url-encoded"),
+        }
+    }
+}
+
+const EMOJI_PRESENTATION: char = '\u{fe0f}';
+const TEXT_PRESENTATION: char = '\u{fe0e}';
+const KEYCAP: char = '\u{20e3}';
+
+/// Whether emojis are displayed colored or as monochrome text, selected with a variation selector.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Default, Clone, Copy)]
+pub enum Presentation {
+    /// As stored in the emoji database.
+    #[default]
+    AsIs,
+    /// Followed by U+FE0F
+    Emoji,
+    /// Followed by U+FE0E
+    Text,
+}
+
+impl Presentation {
+    pub const ALL: [Presentation; 3] =
+        [Presentation::AsIs, Presentation::Emoji, Presentation::Text];
+
+    /// Sets the variation selector of single characters and keycaps which accept one.
+    ///
+    /// Other sequences, e.g. ZWJ sequences or skin tones, are kept as they are,
+    /// since a selector inside them breaks the sequence.
+    pub fn apply(self, s: &str) -> String {
+        self.apply_with(s, data::has_variation_sequences)
+    }
+
+    fn apply_with(self, s: &str, has_variation_sequences: impl Fn(char) -> bool) -> String {
+        let selector = match self {
+            Presentation::AsIs => return s.to_string(),
+            Presentation::Emoji => EMOJI_PRESENTATION,
+            Presentation::Text => TEXT_PRESENTATION,
+        };
+        let mut chars = s
+            .chars()
+            .filter(|&c| c != EMOJI_PRESENTATION && c != TEXT_PRESENTATION);
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(c), None, _) if has_variation_sequences(c) => format!("{c}{selector}"),
+            // keycap bases only form an emoji with U+20E3
+            (Some(c), Some(KEYCAP), None) => format!("{c}{selector}{KEYCAP}"),
+            _ => s.to_string(),
+        }
+    }
+
+    pub fn name(self) -> String {
+        match self {
+            Presentation::AsIs => fl!("presentation-as-is"),
+            Presentation::Emoji => fl!("presentation-emoji"),
+            Presentation::Text => fl!("presentation-text"),
+        }
+    }
+}
+
+/// `<span title="grinning face" role="img" aria-label="grinning face">😀</span>`
+pub fn html(emoji: &str, name: &str) -> String {
+    let name = html_escape(name);
+    format!(r#"<span title="{name}" role="img" aria-label="{name}">{emoji}</span>"#)
+}
+
+fn html_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `U+1F600 U+FE0F`
+pub fn codepoints(s: &str) -> String {
+    s.chars()
+        .map(|c| format!("U+{:X}", c as u32))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the emoji sequences with all skin tones
+    fn sequences() -> impl Iterator<Item = &'static str> {
+        emojis::iter()
+            .flat_map(|emoji| emoji.skin_tones().into_iter().flatten().chain([emoji]))
+            .map(emojis::Emoji::as_str)
+    }
+
+    // an excerpt of emoji-variation-sequences.txt, with characters that have
+    // text presentation by default like ❤ and emoji presentation by default like ☕
+    const VARIATION_SEQUENCES: &str = "\
+# emoji-variation-sequences.txt
+0023 FE0E  ; text style;  # (1.1) NUMBER SIGN
+0023 FE0F  ; emoji style; # (1.1) NUMBER SIGN
+00A9 FE0E  ; text style;  # (1.1) COPYRIGHT SIGN
+00A9 FE0F  ; emoji style; # (1.1) COPYRIGHT SIGN
+231A FE0E  ; text style;  # (1.1) WATCH
+231A FE0F  ; emoji style; # (1.1) WATCH
+2615 FE0E  ; text style;  # (4.0) HOT BEVERAGE
+2615 FE0F  ; emoji style; # (4.0) HOT BEVERAGE
+2648 FE0E  ; text style;  # (1.1) ARIES
+2648 FE0F  ; emoji style; # (1.1) ARIES
+2653 FE0E  ; text style;  # (1.1) PISCES
+2653 FE0F  ; emoji style; # (1.1) PISCES
+26A1 FE0E  ; text style;  # (4.0) HIGH VOLTAGE SIGN
+26A1 FE0F  ; emoji style; # (4.0) HIGH VOLTAGE SIGN
+2764 FE0E  ; text style;  # (1.1) HEAVY BLACK HEART
+2764 FE0F  ; emoji style; # (1.1) HEAVY BLACK HEART
+
+#EOF
+";
+
+    #[test]
+    fn selectors_are_set() {
+        let bases = data::parse_variation_sequences(VARIATION_SEQUENCES);
+        let mut expected: Vec<_> = "#©⌚☕♈♓⚡❤".chars().collect();
+        let mut parsed: Vec<_> = bases.iter().copied().collect();
+        expected.sort();
+        parsed.sort();
+        assert_eq!(parsed, expected);
+
+        let apply = |presentation: Presentation, s: &str| {
+            presentation.apply_with(s, |c| bases.contains(&c))
+        };
+        for base in bases.iter().map(char::to_string) {
+            let emoji = format!("{base}{EMOJI_PRESENTATION}");
+            let text = format!("{base}{TEXT_PRESENTATION}");
+            assert_eq!(apply(Presentation::Emoji, &base), emoji);
+            assert_eq!(apply(Presentation::Emoji, &text), emoji);
+            assert_eq!(apply(Presentation::Text, &base), text);
+            assert_eq!(apply(Presentation::Text, &emoji), text);
+            assert_eq!(apply(Presentation::AsIs, &base), base);
+        }
+        // 😀 has no text presentation
+        assert_eq!(apply(Presentation::Text, "😀"), "😀");
+    }
+
+    #[test]
+    fn keycaps_keep_their_combining_mark() {
+        let keycaps: Vec<_> = sequences()
+            .filter(|emoji| emoji.ends_with(KEYCAP))
+            .collect();
+        assert_eq!(keycaps.len(), 12);
+        for emoji in keycaps {
+            let base = emoji.chars().next().unwrap();
+            assert_eq!(Presentation::Emoji.apply(emoji), emoji);
+            let text = format!("{base}{TEXT_PRESENTATION}{KEYCAP}");
+            assert_eq!(Presentation::Text.apply(emoji), text);
+            assert_eq!(Presentation::Emoji.apply(&text), emoji);
+        }
+    }
+
+    #[test]
+    fn sequences_are_kept() {
+        for emoji in sequences() {
+            let mut chars = emoji.chars().filter(|&c| c != EMOJI_PRESENTATION);
+            let single = chars.next().is_some() && chars.next().is_none();
+            if single || emoji.ends_with(KEYCAP) {
+                continue;
+            }
+            for presentation in Presentation::ALL {
+                let applied = presentation.apply(emoji);
+                assert_eq!(applied, emoji, "{}", emoji.escape_unicode());
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use crate::clipboard;
+
+/// Placeholder in the post-copy command, replaced with the name of the emoji.
+pub const NAME_PLACEHOLDER: &str = "{name}";
+
+/// Runs the post-copy command, e.g. `notify-send Copied {emoji}`.
+pub async fn post_copy(template: String, emoji: String, name: String) -> Result<(), String> {
+    let replacements = [
+        (clipboard::PLACEHOLDER, emoji.as_str()),
+        (NAME_PLACEHOLDER, name.as_str()),
+    ];
+    clipboard::run_template(&template, &replacements, None)
+        .await
+        .map_err(|err| format!("`{template}`: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::tests::{block_on, fake_command, recorded, temp_dir};
+    use std::fs;
+
+    #[test]
+    fn emoji_and_name_are_replaced() {
+        let dir = temp_dir("hook");
+        let hook = fake_command(&dir, "fake-notify");
+
+        let template = format!("{} Copied {{emoji}} {{name}}", hook.display());
+        let copied = post_copy(template, "🦀".into(), "crab".into());
+        assert_eq!(block_on(copied), Ok(()));
+        assert_eq!(recorded(&hook), ("Copied 🦀 crab\n".into(), "".into()));
+
+        let missing = dir.join("missing").display().to_string();
+        let failed = block_on(post_copy(missing.clone(), "🦀".into(), "crab".into()));
+        assert!(failed.unwrap_err().starts_with(&format!("`{missing}`")));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,368 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use cosmic_text::{Attrs, Buffer, Color, Family, Metrics, Shaping, SwashCache};
+use resvg::tiny_skia;
+use resvg::usvg::{self, TreeParsing, TreeTextToPath};
+
+use crate::font::FONT_SYSTEM;
+
+/// Renders `text` in `font_family` into a square RGBA image of `size` pixels.
+pub fn render(text: &str, font_family: &str, size: u16) -> io::Result<Vec<u8>> {
+    let mut font_system = FONT_SYSTEM
+        .lock()
+        .map_err(|_| io::Error::other("font system is poisoned"))?;
+    let mut swash_cache = SwashCache::new();
+    let size_px = f32::from(size);
+    // leave some room for glyphs which exceed their advance
+    let metrics = Metrics::new(size_px * 0.8, size_px);
+    let mut buffer = Buffer::new(&mut font_system, metrics);
+    buffer.set_size(&mut font_system, Some(size_px), Some(size_px));
+    let attrs = Attrs::new().family(Family::Name(font_family));
+    buffer.set_text(&mut font_system, text, attrs, Shaping::Advanced);
+    buffer.shape_until_scroll(&mut font_system, false);
+
+    let line_w = buffer.layout_runs().next().map_or(0.0, |run| run.line_w);
+    let offset_x = ((size_px - line_w) / 2.0).max(0.0) as i32;
+    let size = usize::from(size);
+    let mut pixels = vec![0u8; size * size * 4];
+    buffer.draw(
+        &mut font_system,
+        &mut swash_cache,
+        Color::rgb(0, 0, 0),
+        |x, y, w, h, color| {
+            for py in y..y + h as i32 {
+                for px in x + offset_x..x + offset_x + w as i32 {
+                    if px < 0 || py < 0 || px as usize >= size || py as usize >= size {
+                        continue;
+                    }
+                    let idx = (py as usize * size + px as usize) * 4;
+                    blend(&mut pixels[idx..idx + 4], color);
+                }
+            }
+        },
+    );
+    if pixels.chunks_exact(4).all(|pixel| pixel[3] == 0) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("`{text}` could not be rendered with {font_family}"),
+        ));
+    }
+    Ok(pixels)
+}
+
+/// Renders `text` like [`render`] and encodes it as PNG.
+pub fn render_png(text: &str, font_family: &str, size: u16) -> io::Result<Vec<u8>> {
+    let pixels = render(text, font_family, size)?;
+    encode_png(&pixels, u32::from(size), u32::from(size))
+}
+
+/// Rasterizes an SVG image to fit into a square of `size` pixels and encodes it as PNG.
+pub fn svg_to_png(svg: &[u8], size: u16) -> io::Result<Vec<u8>> {
+    let mut tree = usvg::Tree::from_data(svg, &usvg::Options::default())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    if let Ok(font_system) = FONT_SYSTEM.lock() {
+        tree.convert_text(font_system.db());
+    }
+    let tree = resvg::Tree::from_usvg(&tree);
+    let scale = f32::from(size) / tree.size.width().max(tree.size.height());
+    let width = (tree.size.width() * scale).round().max(1.0) as u32;
+    let height = (tree.size.height() * scale).round().max(1.0) as u32;
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "image is too large"))?;
+    tree.render(
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    pixmap.encode_png().map_err(io::Error::other)
+}
+
+/// Encodes the first frame of a GIF image as PNG.
+pub fn gif_to_png(gif: &[u8]) -> io::Result<Vec<u8>> {
+    let invalid = |err| io::Error::new(io::ErrorKind::InvalidData, err);
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(gif).map_err(invalid)?;
+    let (width, height) = (usize::from(decoder.width()), usize::from(decoder.height()));
+    let frame = decoder
+        .read_next_frame()
+        .map_err(invalid)?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "GIF has no frames"))?;
+    if frame.width == 0 || frame.height == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "GIF frame is empty",
+        ));
+    }
+    // the frame can be smaller than the image
+    let (left, top) = (usize::from(frame.left), usize::from(frame.top));
+    let mut pixels = vec![0u8; width * height * 4];
+    if left < width {
+        let frame_width = usize::from(frame.width);
+        let row_len = frame_width.min(width - left) * 4;
+        for (y, row) in frame.buffer.chunks_exact(frame_width * 4).enumerate() {
+            if top + y >= height {
+                break;
+            }
+            let start = ((top + y) * width + left) * 4;
+            pixels[start..start + row_len].copy_from_slice(&row[..row_len]);
+        }
+    }
+    encode_png(&pixels, width as u32, height as u32)
+}
+
+// RGBA pixels, not premultiplied
+fn encode_png(pixels: &[u8], width: u32, height: u32) -> io::Result<Vec<u8>> {
+    let mut png_bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png_bytes, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(pixels).map_err(io::Error::other)?;
+    drop(writer);
+    Ok(png_bytes)
+}
+
+/// Writes the PNG into `dir`, named after the emoji, and returns the path.
+///
+/// Existing files are kept, e.g. `crab-2.png` is written if `crab.png` exists.
+pub fn save_png(dir: &Path, name: &str, png_bytes: &[u8]) -> io::Result<PathBuf> {
+    let file_stem: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    let (mut file, path) = create_new(dir, &file_stem, "png")?;
+    file.write_all(png_bytes)?;
+    Ok(path)
+}
+
+/// Copies the image file at `path` into `dir` and returns the path of the copy.
+///
+/// Existing files are kept like with [`save_png`].
+pub fn save_file(dir: &Path, path: &Path) -> io::Result<PathBuf> {
+    let file_stem = path
+        .file_stem()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let extension = path.extension().unwrap_or_default();
+    let mut image = std::fs::File::open(path)?;
+    let (mut file, saved) = create_new(
+        dir,
+        &file_stem.to_string_lossy(),
+        &extension.to_string_lossy(),
+    )?;
+    io::copy(&mut image, &mut file)?;
+    Ok(saved)
+}
+
+// creates `<file_stem>.<extension>` in `dir`, or `<file_stem>-2.<extension>` and so on if it exists
+fn create_new(
+    dir: &Path,
+    file_stem: &str,
+    extension: &str,
+) -> io::Result<(std::fs::File, PathBuf)> {
+    std::fs::create_dir_all(dir)?;
+    let file_name = |suffix: String| match extension {
+        "" => format!("{file_stem}{suffix}"),
+        _ => format!("{file_stem}{suffix}.{extension}"),
+    };
+    let mut path = dir.join(file_name(String::new()));
+    let mut n = 1;
+    loop {
+        // `create_new` fails instead of replacing a file written in the meantime
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(file) => return Ok((file, path)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                n += 1;
+                path = dir.join(file_name(format!("-{n}")));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// The directory images are saved to if `image_dir` is not set.
+pub fn default_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("XDG_PICTURES_DIR").filter(|dir| !dir.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    let home = std::env::var("HOME").unwrap_or_else(|e| {
+        eprintln!("failed to read `HOME`: {e}");
+        "/tmp".to_string()
+    });
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map_or_else(|| Path::new(&home).join(".config"), PathBuf::from);
+    std::fs::read_to_string(config_home.join("user-dirs.dirs"))
+        .ok()
+        .and_then(|user_dirs| pictures_dir(&user_dirs, &home))
+        .unwrap_or_else(|| [&home, "Pictures"].iter().collect())
+}
+
+/// Reads lines like `XDG_PICTURES_DIR="$HOME/Pictures"` of `user-dirs.dirs`.
+fn pictures_dir(user_dirs: &str, home: &str) -> Option<PathBuf> {
+    let dir = user_dirs
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("XDG_PICTURES_DIR="))?
+        .trim_matches('"');
+    let dir = match dir.strip_prefix("$HOME") {
+        Some(rest) => PathBuf::from(format!("{home}{rest}")),
+        None => PathBuf::from(dir),
+    };
+    dir.is_absolute().then_some(dir)
+}
+
+// source over destination, both not premultiplied
+fn blend(dst: &mut [u8], src: Color) {
+    let src_a = f32::from(src.a()) / 255.0;
+    let dst_a = f32::from(dst[3]) / 255.0;
+    let out_a = src_a + dst_a * (1.0 - src_a);
+    if out_a <= 0.0 {
+        return;
+    }
+    for (dst_c, src_c) in dst.iter_mut().zip([src.r(), src.g(), src.b()]) {
+        let c = (f32::from(src_c) * src_a + f32::from(*dst_c) * dst_a * (1.0 - src_a)) / out_a;
+        *dst_c = c.round() as u8;
+    }
+    dst[3] = (out_a * 255.0).round() as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_is_rendered() {
+        // any installed font renders a letter
+        let family = FONT_SYSTEM.lock().unwrap().db().faces().next().map(|face| {
+            let (family, _) = &face.families[0];
+            family.clone()
+        });
+        let Some(family) = family else {
+            eprintln!("skipped, no fonts are installed");
+            return;
+        };
+        let pixels = render("A", &family, 32).unwrap();
+        assert_eq!(pixels.len(), 32 * 32 * 4);
+        assert!(pixels.chunks_exact(4).any(|pixel| pixel[3] > 0));
+
+        let png_bytes = render_png("A", &family, 32).unwrap();
+        assert!(png_bytes.starts_with(b"\x89PNG"));
+    }
+
+    fn decode_png(png_bytes: &[u8]) -> (u32, u32, Vec<u8>) {
+        let mut reader = png::Decoder::new(png_bytes).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        (info.width, info.height, pixels)
+    }
+
+    #[test]
+    fn svgs_are_rasterized_to_fit() {
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="36" height="18">
+            <rect width="36" height="18" fill="red"/>
+        </svg>"#;
+        let (width, height, pixels) = decode_png(&svg_to_png(svg, 128).unwrap());
+        assert_eq!((width, height), (128, 64));
+        assert_eq!(pixels[..4], [255, 0, 0, 255]);
+        assert!(svg_to_png(b"<svg", 128).is_err());
+    }
+
+    #[test]
+    fn gifs_are_converted_with_their_first_frame() {
+        // a 3x2 image with a red pixel at 1,1
+        let mut gif_bytes = Vec::new();
+        let mut encoder = gif::Encoder::new(&mut gif_bytes, 3, 2, &[0, 0, 0, 255, 0, 0]).unwrap();
+        let frame = gif::Frame {
+            left: 1,
+            top: 1,
+            width: 1,
+            height: 1,
+            buffer: [1][..].into(),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame).unwrap();
+        drop(encoder);
+
+        let (width, height, pixels) = decode_png(&gif_to_png(&gif_bytes).unwrap());
+        assert_eq!((width, height), (3, 2));
+        let red: Vec<_> = pixels
+            .chunks_exact(4)
+            .map(|pixel| pixel == [255, 0, 0, 255])
+            .collect();
+        assert_eq!(red, [false, false, false, false, true, false]);
+        assert!(gif_to_png(b"GIF").is_err());
+
+        let mut gif_bytes = Vec::new();
+        let mut encoder = gif::Encoder::new(&mut gif_bytes, 3, 2, &[0, 0, 0]).unwrap();
+        let empty = gif::Frame {
+            width: 0,
+            height: 1,
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&empty).unwrap();
+        drop(encoder);
+        assert!(gif_to_png(&gif_bytes).is_err());
+    }
+
+    #[test]
+    fn pictures_dir_is_read_from_user_dirs() {
+        let user_dirs = "# written by xdg-user-dirs-update\n\
+            XDG_DESKTOP_DIR=\"$HOME/Desktop\"\n\
+            XDG_PICTURES_DIR=\"$HOME/Bilder\"\n";
+        assert_eq!(
+            pictures_dir(user_dirs, "/home/user"),
+            Some(PathBuf::from("/home/user/Bilder"))
+        );
+        assert_eq!(
+            pictures_dir("XDG_PICTURES_DIR=\"/data/pictures\"", "/home/user"),
+            Some(PathBuf::from("/data/pictures"))
+        );
+        assert_eq!(
+            pictures_dir("XDG_PICTURES_DIR=\"pictures\"", "/home/user"),
+            None
+        );
+        assert_eq!(
+            pictures_dir("XDG_DESKTOP_DIR=\"$HOME\"", "/home/user"),
+            None
+        );
+    }
+
+    #[test]
+    fn saved_images_are_not_replaced() {
+        let dir =
+            std::env::temp_dir().join(format!("emoji-selector-images-{}", std::process::id()));
+        let first = save_png(&dir, "crab", b"1").unwrap();
+        let second = save_png(&dir, "crab", b"2").unwrap();
+        assert_eq!(first, dir.join("crab.png"));
+        assert_eq!(second, dir.join("crab-2.png"));
+        assert_eq!(std::fs::read(first).unwrap(), b"1");
+        assert_eq!(std::fs::read(second).unwrap(), b"2");
+        assert_eq!(
+            save_png(&dir, "face: smiling", b"").unwrap(),
+            dir.join("face--smiling.png")
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn saved_files_are_not_replaced() {
+        let dir = std::env::temp_dir().join(format!("emoji-selector-files-{}", std::process::id()));
+        let pack_dir = dir.join("pack");
+        std::fs::create_dir_all(&pack_dir).unwrap();
+        let image = pack_dir.join("party.parrot.gif");
+        std::fs::write(&image, b"GIF").unwrap();
+        let first = save_file(&dir, &image).unwrap();
+        let second = save_file(&dir, &image).unwrap();
+        assert_eq!(first, dir.join("party.parrot.gif"));
+        assert_eq!(second, dir.join("party.parrot-2.gif"));
+        assert_eq!(std::fs::read(second).unwrap(), b"GIF");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
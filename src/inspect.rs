@@ -0,0 +1,243 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::data::{self, Emoji, HAIR_STYLES, SKIN_TONES, ZWJ};
+use crate::format::Presentation;
+use crate::symbol;
+
+// the Unicode names of all characters, shared with the symbols
+struct Names {
+    dir: String,
+    names: symbol::Characters,
+}
+
+static NAMES: RwLock<Option<Names>> = RwLock::new(None);
+
+// characters which are easy to miss in pasted text, with their Unicode names
+const INVISIBLES: &[(char, &str)] = &[
+    ('\u{9}', "character tabulation"),
+    ('\u{a}', "line feed"),
+    ('\u{d}', "carriage return"),
+    ('\u{20}', "space"),
+    ('\u{a0}', "no-break space"),
+    ('\u{ad}', "soft hyphen"),
+    ('\u{34f}', "combining grapheme joiner"),
+    ('\u{61c}', "arabic letter mark"),
+    ('\u{180e}', "mongolian vowel separator"),
+    ('\u{2000}', "en quad"),
+    ('\u{2001}', "em quad"),
+    ('\u{2002}', "en space"),
+    ('\u{2003}', "em space"),
+    ('\u{2004}', "three-per-em space"),
+    ('\u{2005}', "four-per-em space"),
+    ('\u{2006}', "six-per-em space"),
+    ('\u{2007}', "figure space"),
+    ('\u{2008}', "punctuation space"),
+    ('\u{2009}', "thin space"),
+    ('\u{200a}', "hair space"),
+    ('\u{200b}', "zero width space"),
+    ('\u{200c}', "zero width non-joiner"),
+    ('\u{200d}', "zero width joiner"),
+    ('\u{200e}', "left-to-right mark"),
+    ('\u{200f}', "right-to-left mark"),
+    ('\u{2028}', "line separator"),
+    ('\u{2029}', "paragraph separator"),
+    ('\u{202a}', "left-to-right embedding"),
+    ('\u{202b}', "right-to-left embedding"),
+    ('\u{202c}', "pop directional formatting"),
+    ('\u{202d}', "left-to-right override"),
+    ('\u{202e}', "right-to-left override"),
+    ('\u{202f}', "narrow no-break space"),
+    ('\u{205f}', "medium mathematical space"),
+    ('\u{2060}', "word joiner"),
+    ('\u{2066}', "left-to-right isolate"),
+    ('\u{2067}', "right-to-left isolate"),
+    ('\u{2068}', "first strong isolate"),
+    ('\u{2069}', "pop directional isolate"),
+    ('\u{3000}', "ideographic space"),
+    ('\u{fe0e}', "variation selector-15"),
+    ('\u{fe0f}', "variation selector-16"),
+    ('\u{feff}', "zero width no-break space"),
+];
+
+/// A grapheme cluster of the inspected text, i.e. what is displayed as one character.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cluster {
+    pub text: String,
+    /// The emoji, if the cluster is one.
+    pub emoji: Option<&'static Emoji>,
+    /// The Unicode name of an invisible character.
+    pub invisible: Option<&'static str>,
+    /// The Unicode name of a single character, once `load_names` succeeded.
+    pub name: Option<String>,
+    /// Whether a ZWJ joins two emojis, also if the sequence is not recommended.
+    pub zwj: bool,
+    pub skin_tones: Vec<char>,
+    /// Set by a variation selector.
+    pub presentation: Option<Presentation>,
+}
+
+impl Cluster {
+    /// Invisible characters are shown as a dotted square.
+    pub fn glyph(&self) -> &str {
+        if self.invisible.is_some() || self.text.chars().all(char::is_control) {
+            "\u{2b1a}"
+        } else {
+            &self.text
+        }
+    }
+}
+
+/// Splits `text` into grapheme clusters.
+pub fn inspect(text: &str) -> Vec<Cluster> {
+    text.graphemes(true)
+        .map(|cluster| {
+            let mut chars = cluster.chars();
+            let single = chars.next().filter(|_| chars.next().is_none());
+            let invisible = single.and_then(|c| {
+                INVISIBLES
+                    .iter()
+                    .find(|(invisible, _)| *invisible == c)
+                    .map(|(_, name)| *name)
+            });
+            let presentation = if cluster.contains('\u{fe0f}') {
+                Some(Presentation::Emoji)
+            } else if cluster.contains('\u{fe0e}') {
+                Some(Presentation::Text)
+            } else {
+                None
+            };
+            Cluster {
+                text: cluster.to_string(),
+                emoji: data::get(cluster),
+                invisible,
+                name: single.and_then(name),
+                zwj: joins_emojis(cluster),
+                skin_tones: cluster.chars().filter(|c| SKIN_TONES.contains(c)).collect(),
+                presentation: presentation.filter(|_| invisible.is_none()),
+            }
+        })
+        .collect()
+}
+
+// a ZWJ after the last emoji, e.g. in pasted text, joins nothing
+fn joins_emojis(cluster: &str) -> bool {
+    let is_emoji = |c: char| HAIR_STYLES.contains(&c) || data::is_rgi(&c.to_string());
+    cluster.contains(ZWJ)
+        && cluster
+            .split(ZWJ)
+            .all(|part| part.chars().next().is_some_and(is_emoji))
+}
+
+/// Loads the names of all characters from `UnicodeData.txt` in `dir`,
+/// unless they are already loaded from there.
+pub fn load_names(dir: &str) {
+    if names_loaded(dir) {
+        return;
+    }
+    let names = symbol::characters(Path::new(dir)).unwrap_or_else(|err| {
+        eprintln!("could not load Unicode names from {dir}: {err}");
+        Arc::new([])
+    });
+    let Ok(mut loaded) = NAMES.write() else {
+        eprintln!("failed to update Unicode names: lock is poisoned");
+        return;
+    };
+    *loaded = Some(Names {
+        dir: dir.to_string(),
+        names,
+    });
+}
+
+/// Whether `load_names` ran with `dir`, also if loading failed.
+pub fn names_loaded(dir: &str) -> bool {
+    NAMES
+        .read()
+        .is_ok_and(|loaded| loaded.as_ref().is_some_and(|loaded| loaded.dir == dir))
+}
+
+fn name(c: char) -> Option<String> {
+    let loaded = NAMES.read().ok()?;
+    let names = &loaded.as_ref()?.names;
+    let i = names.binary_search_by_key(&c, |(c, ..)| *c).ok()?;
+    Some(names[i].1.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zwj(text: &str) -> Vec<bool> {
+        inspect(text).iter().map(|cluster| cluster.zwj).collect()
+    }
+
+    #[test]
+    fn zwj_sequences_are_detected() {
+        assert_eq!(zwj("👩‍🦰"), [true]);
+        assert_eq!(zwj("🏳️‍🌈"), [true]);
+        // not recommended, but still joined
+        assert_eq!(zwj("🦀‍🐂"), [true]);
+        assert_eq!(zwj("👩🦰"), [false, false]);
+    }
+
+    #[test]
+    fn lone_zwjs_join_nothing() {
+        assert_eq!(zwj("a\u{200d}"), [false]);
+        assert_eq!(zwj("👩\u{200d}"), [false]);
+        assert_eq!(zwj("a\u{200d}b"), [false, false]);
+        let clusters = inspect("\u{200d}");
+        assert!(!clusters[0].zwj);
+        assert_eq!(clusters[0].invisible, Some("zero width joiner"));
+    }
+
+    #[test]
+    fn skin_tones_are_detected() {
+        let clusters = inspect("👋🏽🧑🏻‍🤝‍🧑🏿👋");
+        let skin_tones: Vec<_> = clusters.iter().map(|c| c.skin_tones.clone()).collect();
+        assert_eq!(
+            skin_tones,
+            [vec!['\u{1f3fd}'], vec!['\u{1f3fb}', '\u{1f3ff}'], vec![]]
+        );
+        assert!(clusters.iter().all(|cluster| cluster.emoji.is_some()));
+    }
+
+    #[test]
+    fn variation_selectors_set_the_presentation() {
+        let presentations: Vec<_> = inspect("\u{fe0f}☺\u{fe0f}☺\u{fe0e}☺")
+            .iter()
+            .map(|cluster| cluster.presentation)
+            .collect();
+        // a lone variation selector is invisible instead
+        assert_eq!(
+            presentations,
+            [
+                None,
+                Some(Presentation::Emoji),
+                Some(Presentation::Text),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn invisible_characters_are_named() {
+        let clusters = inspect("a\u{200b}\u{a0}\t");
+        let invisibles: Vec<_> = clusters.iter().map(|cluster| cluster.invisible).collect();
+        assert_eq!(
+            invisibles,
+            [
+                None,
+                Some("zero width space"),
+                Some("no-break space"),
+                Some("character tabulation")
+            ]
+        );
+        let glyphs: Vec<_> = clusters.iter().map(Cluster::glyph).collect();
+        assert_eq!(glyphs, ["a", "\u{2b1a}", "\u{2b1a}", "\u{2b1a}"]);
+    }
+}
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use once_cell::sync::Lazy;
+
+use crate::data::{Emoji, Kind};
+
+// kaomoji, name, keywords
+const KAOMOJIS: &[(&str, &str, &[&str])] = &[
+    ("¯\\_(ツ)_/¯", "shrug", &["whatever", "dunno", "idk"]),
+    ("(╯°□°)╯︵ ┻━┻", "table flip", &["angry", "rage", "flip"]),
+    (
+        "┬─┬ノ( º _ ºノ)",
+        "put table back",
+        &["calm", "sorry", "unflip"],
+    ),
+    ("( ͡° ͜ʖ ͡°)", "lenny face", &["lenny", "smirk", "suggestive"]),
+    (
+        "ಠ_ಠ",
+        "look of disapproval",
+        &["disapprove", "stare", "judging"],
+    ),
+    ("(͡•_ ͡• )", "suspicious", &["doubt", "skeptical"]),
+    ("(ง'̀-'́)ง", "fighting", &["fight", "boxing", "ready"]),
+    ("ᕕ( ᐛ )ᕗ", "happy walk", &["happy", "walk", "dance"]),
+    ("ヽ(´▽`)/", "cheering", &["happy", "joy", "yay"]),
+    ("\\(^o^)/", "banzai", &["happy", "excited", "yay"]),
+    ("(^_^)", "smile", &["happy", "smile"]),
+    ("(^_^;)", "nervous smile", &["sweat", "awkward"]),
+    ("(^▽^)", "big smile", &["happy", "grin"]),
+    ("(≧▽≦)", "delighted", &["happy", "excited"]),
+    ("(＾• ω •＾)", "cat face", &["cat", "cute", "uwu"]),
+    ("(=^･ω･^=)", "kitty", &["cat", "cute", "meow"]),
+    ("ʕ•ᴥ•ʔ", "bear", &["bear", "cute", "animal"]),
+    ("(•ᴗ•)", "cute smile", &["happy", "cute"]),
+    ("(◕‿◕)", "sweet smile", &["happy", "cute"]),
+    ("(｡♥‿♥｡)", "in love", &["love", "heart", "crush"]),
+    ("(づ｡◕‿‿◕｡)づ", "hug", &["hug", "love", "cuddle"]),
+    ("(っ˘ω˘ς )", "cuddle", &["hug", "cute"]),
+    ("(´｡• ᵕ •｡`)", "blushing", &["shy", "blush", "cute"]),
+    ("(*^‿^*)", "shy smile", &["shy", "blush"]),
+    ("(⁄ ⁄•⁄ω⁄•⁄ ⁄)", "embarrassed", &["shy", "blush"]),
+    ("(｡•́︿•̀｡)", "sad", &["sad", "pout"]),
+    ("(╥﹏╥)", "crying", &["sad", "tears", "cry"]),
+    ("(T_T)", "tears", &["sad", "cry"]),
+    ("(ಥ﹏ಥ)", "sobbing", &["sad", "cry", "tears"]),
+    ("(>_<)", "frustrated", &["annoyed", "pain"]),
+    ("(-_-)", "unamused", &["bored", "meh"]),
+    ("(-_-;)", "uneasy", &["sweat", "awkward"]),
+    ("(¬_¬)", "side eye", &["suspicious", "annoyed"]),
+    ("(ーー;)", "worried", &["sweat", "uneasy"]),
+    ("(o_O)", "confused", &["surprised", "what"]),
+    ("(⊙_⊙)", "shocked", &["surprised", "stare"]),
+    ("(°ロ°)", "astonished", &["surprised", "shock"]),
+    ("Σ(°△°|||)", "horrified", &["shock", "scared"]),
+    ("(ノಠ益ಠ)ノ", "furious", &["angry", "rage"]),
+    ("(＃`Д´)", "angry", &["mad", "rage"]),
+    ("(￣^￣)", "pouting", &["proud", "hmph"]),
+    ("(￣ω￣)", "smug", &["content", "smirk"]),
+    ("(•̀ᴗ•́)و", "determined", &["motivated", "fighting", "yes"]),
+    ("(☞ﾟヮﾟ)☞", "pointing", &["you", "this"]),
+    ("(⌐■_■)", "cool", &["sunglasses", "deal with it"]),
+    (
+        "( •_•)>⌐■-■",
+        "putting on sunglasses",
+        &["cool", "deal with it"],
+    ),
+    ("(-‿‿-)", "content", &["calm", "peace"]),
+    ("(－_－) zzZ", "sleeping", &["sleep", "tired"]),
+    ("(*_*)", "starstruck", &["amazed", "dazzled"]),
+    ("(^_-)", "wink", &["wink", "flirt"]),
+    ("(^_^)/", "waving", &["hello", "hi", "bye"]),
+    ("(・_・)ノ", "hi", &["hello", "wave"]),
+    ("m(_ _)m", "bowing", &["sorry", "thanks", "please"]),
+    ("(シ_ _)シ", "kneeling", &["sorry", "apology"]),
+    ("(＾▽＾)っ✂╰⋃╯", "scissors", &["cut"]),
+    ("<(￣︶￣)>", "satisfied", &["proud", "content"]),
+    ("♪(´ε` )", "whistling", &["music", "innocent"]),
+    ("ヾ(⌐■_■)ノ♪", "dancing", &["music", "party", "cool"]),
+    ("~(˘▾˘~)", "wiggle", &["dance", "happy"]),
+    ("<3", "heart", &["love"]),
+    (":)", "smiley", &["happy", "smile", "emoticon"]),
+    (":(", "frowny", &["sad", "emoticon"]),
+    (";)", "winky", &["wink", "emoticon"]),
+    (":D", "laughing", &["happy", "grin", "emoticon"]),
+    (":P", "tongue out", &["silly", "emoticon"]),
+    (":O", "surprised", &["shock", "emoticon"]),
+    ("XD", "laughing hard", &["lol", "emoticon"]),
+    ("o/", "wave", &["hello", "bye", "emoticon"]),
+];
+
+static ENTRIES: Lazy<Vec<Emoji>> = Lazy::new(|| {
+    KAOMOJIS
+        .iter()
+        .map(|(kaomoji, name, keywords)| Emoji::text(Kind::Kaomoji, kaomoji, name, keywords))
+        .collect()
+});
+
+/// The built-in kaomojis and emoticons.
+pub fn iter() -> impl Iterator<Item = &'static Emoji> {
+    ENTRIES.iter()
+}
+
+pub fn get(s: &str) -> Option<&'static Emoji> {
+    iter().find(|kaomoji| kaomoji.as_str() == s)
+}
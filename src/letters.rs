@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use crate::fl;
+
+const REGIONAL_INDICATOR_A: u32 = 0x1f1e6;
+const NEGATIVE_SQUARED_A: u32 = 0x1f170;
+const EMOJI_PRESENTATION: char = '\u{fe0f}';
+const KEYCAP: char = '\u{20e3}';
+// keeps regional indicators from being rendered as flags
+const SEPARATOR: char = '\u{200b}';
+
+/// How letters and digits are turned into emojis, e.g. `HI 42`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// 🇭​🇮 4️⃣2️⃣
+    RegionalIndicator,
+    /// 🅷🅸 4️⃣2️⃣
+    Squared,
+}
+
+impl Style {
+    pub const ALL: [Style; 2] = [Style::RegionalIndicator, Style::Squared];
+
+    pub fn name(self) -> String {
+        match self {
+            Style::RegionalIndicator => fl!("letters-regional-indicator"),
+            Style::Squared => fl!("letters-squared"),
+        }
+    }
+
+    /// Replaces letters, digits, `#`, `*`, `!` and `?`, other characters are kept.
+    pub fn generate(self, text: &str) -> String {
+        let mut generated = String::with_capacity(text.len() * 8);
+        let mut previous_letter = false;
+        for c in text.chars() {
+            let upper = c.to_ascii_uppercase();
+            let is_letter = upper.is_ascii_uppercase();
+            match self {
+                Style::RegionalIndicator if is_letter => {
+                    if previous_letter {
+                        generated.push(SEPARATOR);
+                    }
+                    generated.push(offset_char(REGIONAL_INDICATOR_A, upper));
+                }
+                Style::Squared if is_letter => {
+                    generated.push(offset_char(NEGATIVE_SQUARED_A, upper));
+                    // 🅰️ 🅱️ 🅾️ 🅿️ are displayed as text otherwise
+                    if matches!(upper, 'A' | 'B' | 'O' | 'P') {
+                        generated.push(EMOJI_PRESENTATION);
+                    }
+                }
+                _ => match c {
+                    '0'..='9' | '#' | '*' => {
+                        generated.push(c);
+                        generated.push(EMOJI_PRESENTATION);
+                        generated.push(KEYCAP);
+                    }
+                    '!' => generated.push('❗'),
+                    '?' => generated.push('❓'),
+                    _ => generated.push(c),
+                },
+            }
+            previous_letter = is_letter;
+        }
+        generated
+    }
+}
+
+fn offset_char(first: u32, letter: char) -> char {
+    char::from_u32(first + (letter as u32 - 'A' as u32)).unwrap_or(letter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regional_indicators_are_separated() {
+        assert_eq!(
+            Style::RegionalIndicator.generate("Hi de!"),
+            "🇭\u{200b}🇮 🇩\u{200b}🇪❗"
+        );
+    }
+
+    #[test]
+    fn squared_letters_have_emoji_presentation() {
+        assert_eq!(
+            Style::Squared.generate("Bob?"),
+            "🅱\u{fe0f}🅾\u{fe0f}🅱\u{fe0f}❓"
+        );
+        assert_eq!(Style::Squared.generate("hi"), "🅷🅸");
+    }
+
+    #[test]
+    fn digits_are_keycaps() {
+        for style in Style::ALL {
+            assert_eq!(
+                style.generate("#4*"),
+                "#\u{fe0f}\u{20e3}4\u{fe0f}\u{20e3}*\u{fe0f}\u{20e3}"
+            );
+            assert_eq!(style.generate("ä-€"), "ä-€");
+        }
+    }
+}
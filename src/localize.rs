@@ -4,11 +4,23 @@ use i18n_embed::{
 };
 use once_cell::sync::Lazy;
 use rust_embed::RustEmbed;
+use {
+    crate::config::Annotation,
+    fluent_langneg::LanguageIdentifier,
+    std::{collections::HashMap, io},
+};
 
 #[derive(RustEmbed)]
 #[folder = "i18n/"]
 struct Localizations;
 
+// compressed in release builds; debug builds read the files from the source tree at runtime,
+// so a debug binary moved away from the checkout has no fallback
+#[cfg(feature = "embed-annotations")]
+#[derive(RustEmbed)]
+#[folder = "i18n-json/"]
+struct Annotations;
+
 pub static LANGUAGE_LOADER: Lazy<FluentLanguageLoader> = Lazy::new(|| {
     let loader: FluentLanguageLoader = fluent_language_loader!();
 
@@ -43,3 +55,107 @@ pub fn localize() {
         eprintln!("Error while loading language for App List {}", error);
     }
 }
+
+/// Merges the annotations of the `locales` matching the requested languages, so that
+/// the most preferred language wins. `read` returns the `annotations.json` of a locale.
+pub fn annotations(
+    locales: Vec<String>,
+    requested_languages: &[LanguageIdentifier],
+    default_language: &LanguageIdentifier,
+    read: impl Fn(&str) -> io::Result<Vec<u8>>,
+) -> HashMap<String, Annotation> {
+    let available_languages = fluent_langneg::convert_vec_str_to_langids_lossy(locales);
+    let supported_languages = fluent_langneg::negotiate_languages(
+        requested_languages,
+        &available_languages,
+        Some(default_language),
+        fluent_langneg::NegotiationStrategy::Filtering,
+    );
+    let mut annotations = HashMap::new();
+    for lang_code in supported_languages.into_iter().rev() {
+        let lang_code = lang_code.to_string();
+        let file_contents = match read(&lang_code) {
+            Ok(ok) => ok,
+            Err(e) => {
+                eprintln!("could not read annotations.json file: {lang_code} - {e}");
+                continue;
+            }
+        };
+        match serde_json::from_slice::<HashMap<String, Annotation>>(&file_contents) {
+            Ok(annotations_locale) => annotations.extend(annotations_locale),
+            Err(e) => eprintln!("could not parse annotations.json file: {lang_code} - {e}"),
+        }
+    }
+    annotations
+}
+
+/// The annotations compiled into the binary, for when `i18n-json` is not installed.
+#[cfg(feature = "embed-annotations")]
+pub fn embedded_annotations(
+    requested_languages: &[LanguageIdentifier],
+    default_language: &LanguageIdentifier,
+) -> HashMap<String, Annotation> {
+    let locales = Annotations::iter()
+        .filter_map(|path| Some(path.split_once('/')?.0.to_string()))
+        .collect();
+    annotations(
+        locales,
+        requested_languages,
+        default_language,
+        |lang_code| {
+            Annotations::get(&format!("{lang_code}/annotations.json"))
+                .map(|file| file.data.into_owned())
+                .ok_or_else(|| io::ErrorKind::NotFound.into())
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn languages(languages: &[&str]) -> Vec<LanguageIdentifier> {
+        languages.iter().map(|lang| lang.parse().unwrap()).collect()
+    }
+
+    // every locale names the crab after itself, only `en` has the ox
+    fn read(lang_code: &str) -> io::Result<Vec<u8>> {
+        match lang_code {
+            "en" => Ok(r#"{"🦀": {"tts": ["en"]}, "🐂": {"tts": ["en"]}}"#.into()),
+            "de" | "de-CH" => Ok(format!(r#"{{"🦀": {{"tts": ["{lang_code}"]}}}}"#).into_bytes()),
+            "broken" => Ok(b"{".to_vec()),
+            _ => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+
+    fn tts(requested: &[&str]) -> (String, String) {
+        let locales = ["en", "de", "de-CH", "broken", "missing"];
+        let locales = locales.iter().map(|lang| lang.to_string()).collect();
+        let default_language = "en".parse().unwrap();
+        let annotations = annotations(locales, &languages(requested), &default_language, read);
+        let tts = |emoji: &str| annotations[emoji].tts.join(" ");
+        (tts("🦀"), tts("🐂"))
+    }
+
+    #[test]
+    fn preferred_languages_win() {
+        assert_eq!(tts(&["de-CH"]), ("de-CH".into(), "en".into()));
+        assert_eq!(tts(&["de", "de-CH"]), ("de".into(), "en".into()));
+    }
+
+    #[test]
+    fn english_is_the_fallback() {
+        assert_eq!(tts(&["fr"]), ("en".into(), "en".into()));
+        assert_eq!(tts(&[]), ("en".into(), "en".into()));
+        // unreadable locales are skipped
+        assert_eq!(tts(&["broken"]), ("en".into(), "en".into()));
+        assert_eq!(tts(&["missing"]), ("en".into(), "en".into()));
+    }
+
+    #[cfg(feature = "embed-annotations")]
+    #[test]
+    fn annotations_are_embedded() {
+        let annotations = embedded_annotations(&languages(&["de"]), &"en".parse().unwrap());
+        assert_eq!(annotations["🦀"].tts, ["Krebs"]);
+    }
+}
@@ -1,26 +1,40 @@
 // SPDX-License-Identifier: MPL-2.0
 // 2024 - Dominic Gerhauser and contributors
 
-use std::collections::HashMap;
 use std::path::PathBuf;
 use std::{env, fs};
 
 use crate::window::Window;
 
-use config::{Annotation, Config, CONFIG_VERSION};
+use config::{Config, CONFIG_VERSION};
 use cosmic::cosmic_config;
 use cosmic::cosmic_config::CosmicConfigEntry;
 mod config;
 use window::Flags;
 
+mod clipboard;
+mod data;
+mod emojify;
+mod flag;
+mod font;
+mod format;
+mod hook;
+mod image;
+mod inspect;
+mod kaomoji;
+mod letters;
 mod localize;
+mod pack;
+mod search;
+mod snippet;
+mod symbol;
+mod typer;
 mod widget_copy;
 mod window;
 
 fn main() -> cosmic::iced::Result {
     localize::localize();
 
-    let mut annotations = HashMap::new();
     let xdg_data_dir = env::var("XDG_DATA_DIRS").unwrap_or_else(|e| {
         eprintln!("failed to read `XDG_DATA_DIRS`: {e}");
         "/usr/share:/usr/locale/share".to_string()
@@ -36,9 +50,9 @@ fn main() -> cosmic::iced::Result {
     );
 
     let default_language: fluent_langneg::LanguageIdentifier = "en".parse().unwrap();
-    if let Some(dir) = xdg_data_dir {
+    let annotations = if let Some(dir) = xdg_data_dir {
         let i18n_json_dir: PathBuf = [dir, window::ID, "i18n-json"].iter().collect();
-        let locales_in_dir = match fs::read_dir(i18n_json_dir) {
+        let locales_in_dir = match fs::read_dir(&i18n_json_dir) {
             Ok(dir_iter) => dir_iter
                 .filter_map(|file_res| match file_res {
                     Ok(file) => Some(
@@ -59,40 +73,23 @@ fn main() -> cosmic::iced::Result {
             }
         };
 
-        let available_languages = fluent_langneg::convert_vec_str_to_langids_lossy(locales_in_dir);
-        let supported_languages = fluent_langneg::negotiate_languages(
+        localize::annotations(
+            locales_in_dir,
             &requested_languages,
-            &available_languages,
-            Some(&default_language),
-            fluent_langneg::NegotiationStrategy::Filtering,
-        );
-        for lang_code in supported_languages.into_iter().rev() {
-            let lang_code = lang_code.to_string();
-            let annotation_file: PathBuf =
-                [dir, window::ID, "i18n-json", &lang_code, "annotations.json"]
-                    .iter()
-                    .collect();
-            let file_contents = match fs::read(&annotation_file) {
-                Ok(ok) => ok,
-                Err(e) => {
-                    eprintln!("could not read annotations.json file: {annotation_file:?} - {lang_code} - {e}");
-                    continue;
-                }
-            };
-
-            let annotations_locale: HashMap<String, Annotation> = match serde_json::from_slice(
-                &file_contents,
-            ) {
-                Ok(ok) => ok,
-                Err(e) => {
-                    eprintln!("could not parse annotations.json file: {annotation_file:?} {lang_code} - {e}");
-                    continue;
-                }
-            };
-
-            annotations.extend(annotations_locale);
+            &default_language,
+            |lang_code| fs::read(i18n_json_dir.join(lang_code).join("annotations.json")),
+        )
+    } else {
+        #[cfg(feature = "embed-annotations")]
+        {
+            localize::embedded_annotations(&requested_languages, &default_language)
         }
-    }
+        #[cfg(not(feature = "embed-annotations"))]
+        {
+            eprintln!("no i18n-json directory in `XDG_DATA_DIRS`, emoji names are not localized");
+            Default::default()
+        }
+    };
 
     let (config_handler, config) = match cosmic_config::Config::new(window::ID, CONFIG_VERSION) {
         Ok(config_handler) => {
@@ -110,6 +107,8 @@ fn main() -> cosmic::iced::Result {
             (None, Config::default())
         }
     };
+    data::init(&config.unicode_data_dir);
+    snippet::set(&config.snippets);
     let flags = Flags {
         config_handler,
         config,
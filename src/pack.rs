@@ -0,0 +1,406 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use std::any::TypeId;
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use cosmic::iced::subscription;
+use cosmic::iced_futures::futures::{channel::mpsc, SinkExt, StreamExt};
+use cosmic::iced_futures::Subscription;
+use notify::Watcher;
+use serde::{Deserialize, Serialize};
+
+use crate::clipboard;
+use crate::data::{self, Emoji, Kind};
+use crate::image;
+use crate::window::ID;
+
+/// Optional file in a pack directory with the names and keywords of the images.
+pub const MANIFEST: &str = "manifest.json";
+// the events of saving a file are bundled into one reload
+const DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(500);
+
+static PACKS: RwLock<Vec<Pack>> = RwLock::new(Vec::new());
+
+/// A directory of custom image emojis, shown as an additional tab.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pack {
+    pub name: String,
+    pub icon: Option<PathBuf>,
+    pub emojis: Vec<&'static Emoji>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct Manifest {
+    #[serde(default)]
+    name: String,
+    /// File of the tab icon, defaults to the first image.
+    #[serde(default)]
+    icon: String,
+    /// Defaults to all images in the directory.
+    #[serde(default)]
+    emojis: Vec<ManifestEmoji>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct ManifestEmoji {
+    file: String,
+    /// Defaults to the file name.
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    keywords: Vec<String>,
+}
+
+/// How an image emoji is copied.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Default, Clone, Copy)]
+pub enum PackCopy {
+    /// The image file, along with its location for file managers.
+    #[default]
+    Image,
+    /// The `file://` URI of the image.
+    FileUri,
+}
+
+/// `~/.local/share/<ID>/packs`
+pub fn dir() -> PathBuf {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+        .unwrap_or_default();
+    data_home.join(ID).join("packs")
+}
+
+/// Loads all packs in `dir`, replacing the previous ones.
+pub fn reload() -> Vec<Pack> {
+    let dir = dir();
+    let mut pack_dirs: Vec<_> = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect(),
+        Err(err) => {
+            if err.kind() != io::ErrorKind::NotFound {
+                eprintln!("could not read emoji packs from {}: {err}", dir.display());
+            }
+            Vec::new()
+        }
+    };
+    pack_dirs.sort();
+    let Ok(mut packs) = PACKS.write() else {
+        eprintln!("failed to update emoji packs: lock is poisoned");
+        return Vec::new();
+    };
+    *packs = pack_dirs
+        .iter()
+        .filter_map(|pack_dir| match load(pack_dir) {
+            Ok(pack) => Some(pack),
+            Err(err) => {
+                eprintln!("could not load emoji pack {}: {err}", pack_dir.display());
+                None
+            }
+        })
+        .collect();
+    packs.clone()
+}
+
+fn load(pack_dir: &Path) -> io::Result<Pack> {
+    let manifest = match std::fs::read_to_string(pack_dir.join(MANIFEST)) {
+        Ok(manifest) => serde_json::from_str(&manifest)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Manifest::default(),
+        Err(err) => return Err(err),
+    };
+    let mut entries = manifest.emojis;
+    if entries.is_empty() {
+        let mut files: Vec<_> = std::fs::read_dir(pack_dir)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| mime_type(path).is_some())
+            .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+            .collect();
+        files.sort();
+        entries = files
+            .into_iter()
+            .map(|file| ManifestEmoji {
+                file,
+                ..Default::default()
+            })
+            .collect();
+    }
+    let emojis: Vec<_> = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let path = pack_dir.join(&entry.file);
+            if mime_type(&path).is_none() || !path.is_file() {
+                eprintln!("unsupported or missing image {}", path.display());
+                return None;
+            }
+            let shortcode = Path::new(&entry.file).file_stem()?.to_str()?;
+            let name = if entry.name.is_empty() {
+                shortcode.replace(['-', '_'], " ")
+            } else {
+                entry.name
+            };
+            let keywords: Vec<_> = entry.keywords.iter().map(String::as_str).collect();
+            let emoji = Emoji::text(Kind::Image, &format!(":{shortcode}:"), &name, &keywords)
+                .with_image(path);
+            Some(data::intern(emoji))
+        })
+        .collect();
+    let name = if manifest.name.is_empty() {
+        pack_dir
+            .file_name()
+            .map_or_else(String::new, |name| name.to_string_lossy().into_owned())
+    } else {
+        manifest.name
+    };
+    let icon = if manifest.icon.is_empty() {
+        emojis
+            .first()
+            .and_then(|emoji| emoji.image())
+            .map(Path::to_path_buf)
+    } else {
+        Some(pack_dir.join(manifest.icon))
+    };
+    Ok(Pack { name, icon, emojis })
+}
+
+/// Looks up an image emoji by its `:shortcode:`.
+pub fn get(s: &str) -> Option<&'static Emoji> {
+    let packs = PACKS.read().ok()?;
+    packs
+        .iter()
+        .flat_map(|pack| pack.emojis.iter())
+        .find(|emoji| emoji.as_str() == s)
+        .copied()
+}
+
+pub fn mime_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let mime_type = match extension.as_str() {
+        "png" => "image/png",
+        "svg" => "image/svg+xml",
+        "gif" => "image/gif",
+        _ => return None,
+    };
+    Some(mime_type)
+}
+
+/// `file:///home/user/a%20b.png`
+pub fn file_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for b in path.to_string_lossy().bytes() {
+        if b.is_ascii_alphanumeric() || b"-_.~/".contains(&b) {
+            uri.push(b as char);
+        } else {
+            _ = write!(uri, "%{b:02X}");
+        }
+    }
+    uri
+}
+
+/// The clipboard contents for the image at `path`.
+pub fn clipboard_data(path: &Path, copy: PackCopy, size: u16) -> io::Result<clipboard::Data> {
+    let uri = file_uri(path);
+    let uri_list = format!("{uri}\r\n").into_bytes();
+    match copy {
+        PackCopy::Image => {
+            let mime_type = mime_type(path).unwrap_or("application/octet-stream");
+            let bytes = std::fs::read(path)?;
+            // most applications only paste PNG images, SVGs are rasterized with `size` pixels
+            let png_bytes = match mime_type {
+                "image/svg+xml" => Some(image::svg_to_png(&bytes, size)),
+                "image/gif" => Some(image::gif_to_png(&bytes)),
+                _ => None,
+            };
+            let data = match png_bytes {
+                Some(Ok(png_bytes)) => {
+                    clipboard::Data::new("image/png", png_bytes).with(mime_type, bytes)
+                }
+                Some(Err(err)) => {
+                    eprintln!("failed to convert {} to PNG: {err}", path.display());
+                    clipboard::Data::new(mime_type, bytes)
+                }
+                None => clipboard::Data::new(mime_type, bytes),
+            };
+            Ok(data.with("text/uri-list", uri_list))
+        }
+        PackCopy::FileUri => Ok(clipboard::Data::new("text/uri-list", uri_list)
+            .with("text/plain;charset=utf-8", uri.into_bytes())),
+    }
+}
+
+/// Sends `message` whenever the pack directory changes.
+pub fn subscription<Message: Clone + Send + 'static>(message: Message) -> Subscription<Message> {
+    struct PackSubscription;
+    subscription::channel(
+        TypeId::of::<PackSubscription>(),
+        1,
+        |mut output| async move {
+            let dir = dir();
+            // most users have no packs, the directory is not created for them
+            if !dir.is_dir() {
+                return std::future::pending().await;
+            }
+            let (tx, mut rx) = mpsc::unbounded();
+            let watcher =
+                notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                    if event.is_ok_and(|event| !event.kind.is_access()) {
+                        _ = tx.unbounded_send(());
+                    }
+                })
+                .and_then(|mut watcher| {
+                    watcher.watch(&dir, notify::RecursiveMode::Recursive)?;
+                    Ok(watcher)
+                });
+            // watching stops when the watcher is dropped
+            let _watcher = match watcher {
+                Ok(watcher) => watcher,
+                Err(err) => {
+                    eprintln!("failed to watch emoji packs in {}: {err}", dir.display());
+                    return std::future::pending().await;
+                }
+            };
+            loop {
+                if rx.next().await.is_none() {
+                    return std::future::pending().await;
+                }
+                tokio::time::sleep(DEBOUNCE).await;
+                while rx.try_next().is_ok_and(|event| event.is_some()) {}
+                if let Err(err) = output.send(message.clone()).await {
+                    eprintln!("failed to reload emoji packs: {err}");
+                }
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn pack_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("emoji-selector-pack-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn mime_types(data: &clipboard::Data) -> Vec<&str> {
+        data.types.iter().map(|(mime, _)| mime.as_str()).collect()
+    }
+
+    #[test]
+    fn manifests_name_the_images() {
+        let dir = pack_dir("manifest");
+        for file in ["parrot.gif", "cat.png", "notes.txt"] {
+            fs::write(dir.join(file), b"").unwrap();
+        }
+        let manifest = r#"{
+            "name": "Party",
+            "icon": "cat.png",
+            "emojis": [
+                { "file": "parrot.gif", "name": "party parrot", "keywords": ["bird"] },
+                { "file": "missing.png" },
+                { "file": "notes.txt" }
+            ]
+        }"#;
+        fs::write(dir.join(MANIFEST), manifest).unwrap();
+        let pack = load(&dir).unwrap();
+        assert_eq!(pack.name, "Party");
+        assert_eq!(pack.icon, Some(dir.join("cat.png")));
+        let emojis: Vec<_> = pack
+            .emojis
+            .iter()
+            .map(|emoji| (emoji.as_str(), emoji.name(), emoji.keywords()))
+            .collect();
+        assert_eq!(
+            emojis,
+            [(":parrot:", "party parrot", &["bird".to_string()][..])]
+        );
+        assert_eq!(pack.emojis[0].image(), Some(&*dir.join("parrot.gif")));
+
+        fs::write(dir.join(MANIFEST), "{").unwrap();
+        assert_eq!(load(&dir).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn images_are_named_after_their_files() {
+        let dir = pack_dir("files");
+        for file in ["thumbs_up.PNG", "party-parrot.gif", "logo.svg", "notes.txt"] {
+            fs::write(dir.join(file), b"").unwrap();
+        }
+        let pack = load(&dir).unwrap();
+        assert_eq!(pack.name, dir.file_name().unwrap().to_string_lossy());
+        let emojis: Vec<_> = pack
+            .emojis
+            .iter()
+            .map(|emoji| (emoji.as_str(), emoji.name()))
+            .collect();
+        assert_eq!(
+            emojis,
+            [
+                (":logo:", "logo"),
+                (":party-parrot:", "party parrot"),
+                (":thumbs_up:", "thumbs up"),
+            ]
+        );
+        assert_eq!(pack.icon, Some(dir.join("logo.svg")));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn file_uris_are_escaped() {
+        assert_eq!(
+            file_uri(Path::new("/home/user/a b/ä#1.png")),
+            "file:///home/user/a%20b/%C3%A4%231.png"
+        );
+    }
+
+    #[test]
+    fn images_are_offered_as_png() {
+        let dir = pack_dir("clipboard");
+        let png = dir.join("crab.png");
+        let svg = dir.join("crab.svg");
+        let gif = dir.join("crab.gif");
+        fs::write(&png, b"PNG").unwrap();
+        fs::write(
+            &svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="1" height="1"/>"#,
+        )
+        .unwrap();
+        // not a valid GIF, so it is only offered as is
+        fs::write(&gif, b"GIF").unwrap();
+
+        let data = clipboard_data(&png, PackCopy::Image, 32).unwrap();
+        assert_eq!(mime_types(&data), ["image/png", "text/uri-list"]);
+        assert_eq!(data.types[0].1, b"PNG");
+        let uri_list = format!("{}\r\n", file_uri(&png));
+        assert_eq!(data.types[1].1, uri_list.as_bytes());
+
+        let data = clipboard_data(&svg, PackCopy::Image, 32).unwrap();
+        assert_eq!(
+            mime_types(&data),
+            ["image/png", "image/svg+xml", "text/uri-list"]
+        );
+        let data = clipboard_data(&gif, PackCopy::Image, 32).unwrap();
+        assert_eq!(mime_types(&data), ["image/gif", "text/uri-list"]);
+
+        let data = clipboard_data(&png, PackCopy::FileUri, 32).unwrap();
+        assert_eq!(
+            mime_types(&data),
+            ["text/uri-list", "text/plain;charset=utf-8"]
+        );
+        assert_eq!(data.types[1].1, file_uri(&png).as_bytes());
+
+        assert!(clipboard_data(&dir.join("missing.png"), PackCopy::Image, 32).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
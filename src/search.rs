@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use emojis::UnicodeVersion;
+
+use crate::data;
+
+/// Prefix of the search operator for the maximum Unicode version, e.g. `v:13.0`.
+pub const VERSION_OPERATOR: &str = "v:";
+
+/// The search input with the operators removed from the text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    pub text: String,
+    pub max_version: Option<UnicodeVersion>,
+}
+
+impl Query {
+    pub fn parse(search: &str) -> Self {
+        let mut query = Query::default();
+        let mut has_operator = false;
+        let mut words = Vec::new();
+        for word in search.split_whitespace() {
+            if let Some(version) = word
+                .strip_prefix(VERSION_OPERATOR)
+                .and_then(data::parse_version)
+            {
+                query.max_version = Some(version);
+                has_operator = true;
+                continue;
+            }
+            words.push(word);
+        }
+        query.text = if has_operator {
+            words.join(" ")
+        } else {
+            search.to_string()
+        };
+        query
+    }
+
+    /// The lower of `max_unicode_version` from the config and the version of the operator.
+    pub fn max_version(&self, max_unicode_version: &str) -> Option<UnicodeVersion> {
+        data::parse_version(max_unicode_version)
+            .into_iter()
+            .chain(self.max_version)
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_operator_is_parsed() {
+        for search in ["v:13", "v:13.0"] {
+            let query = Query::parse(search);
+            assert_eq!(query.text, "");
+            assert_eq!(query.max_version, Some(UnicodeVersion::new(13, 0)));
+        }
+        let query = Query::parse("v:15.1");
+        assert_eq!(query.max_version, Some(UnicodeVersion::new(15, 1)));
+    }
+
+    #[test]
+    fn invalid_versions_are_searched() {
+        for search in ["v:", "v:abc", "v:13.", "v:13.x"] {
+            let query = Query::parse(search);
+            assert_eq!(query.text, search);
+            assert_eq!(query.max_version, None);
+        }
+    }
+
+    #[test]
+    fn version_operator_is_removed_from_the_words() {
+        let query = Query::parse("red  v:12 heart");
+        assert_eq!(query.text, "red heart");
+        assert_eq!(query.max_version, Some(UnicodeVersion::new(12, 0)));
+        // without an operator the search is kept as typed
+        let query = Query::parse(" red  heart ");
+        assert_eq!(query.text, " red  heart ");
+        assert_eq!(query.max_version, None);
+    }
+
+    #[test]
+    fn lower_version_wins() {
+        let query = Query::parse("v:13");
+        assert_eq!(query.max_version("14.0"), Some(UnicodeVersion::new(13, 0)));
+        assert_eq!(query.max_version("12.1"), Some(UnicodeVersion::new(12, 1)));
+        assert_eq!(query.max_version(""), Some(UnicodeVersion::new(13, 0)));
+        let query = Query::parse("heart");
+        assert_eq!(query.max_version("12.1"), Some(UnicodeVersion::new(12, 1)));
+        assert_eq!(query.max_version(""), None);
+    }
+}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use std::sync::{Arc, RwLock};
+
+use once_cell::sync::Lazy;
+
+use crate::config::Snippet;
+use crate::data::{self, Emoji, Kind};
+
+static SNIPPETS: Lazy<RwLock<Arc<[&'static Emoji]>>> = Lazy::new(|| RwLock::new(Arc::new([])));
+
+/// Replaces the snippets.
+pub fn set(snippets: &[Snippet]) {
+    let Ok(mut entries) = SNIPPETS.write() else {
+        eprintln!("failed to update snippets: lock is poisoned");
+        return;
+    };
+    *entries = snippets
+        .iter()
+        .filter(|snippet| !snippet.text.is_empty())
+        .map(|snippet| {
+            let name = if snippet.name.is_empty() {
+                &snippet.text
+            } else {
+                &snippet.name
+            };
+            let keywords: Vec<_> = snippet.keywords.iter().map(String::as_str).collect();
+            data::intern(Emoji::text(Kind::Custom, &snippet.text, name, &keywords))
+        })
+        .collect();
+}
+
+pub fn iter() -> impl Iterator<Item = &'static Emoji> {
+    let entries = SNIPPETS
+        .read()
+        .map(|entries| entries.clone())
+        .unwrap_or_else(|_| Arc::new([]));
+    (0..entries.len()).map(move |i| entries[i])
+}
+
+pub fn get(s: &str) -> Option<&'static Emoji> {
+    let entries = SNIPPETS.read().ok()?;
+    entries
+        .iter()
+        .find(|snippet| snippet.as_str() == s)
+        .copied()
+}
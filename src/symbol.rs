@@ -0,0 +1,258 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use crate::data::{self, Emoji, Kind};
+
+// the configuration the symbols were loaded with
+struct Symbols {
+    dir: String,
+    blocks: Vec<String>,
+    symbols: Arc<[&'static Emoji]>,
+}
+
+/// The characters of `UnicodeData.txt` with their names, ordered by character, and whether
+/// they can be shown alone, unlike control, format, separator and combining characters.
+pub type Characters = Arc<[(char, String, bool)]>;
+
+// the characters are shared by the symbols and the inspector
+struct UnicodeData {
+    dir: PathBuf,
+    characters: Characters,
+}
+
+static SYMBOLS: RwLock<Option<Symbols>> = RwLock::new(None);
+static UNICODE_DATA_CACHE: RwLock<Option<UnicodeData>> = RwLock::new(None);
+
+/// File of the Unicode character database with the character names.
+pub const UNICODE_DATA: &str = "UnicodeData.txt";
+/// File of the Unicode character database with the block ranges.
+pub const BLOCKS: &str = "Blocks.txt";
+
+/// Loads the characters of `blocks` in the given order, sectioned by block.
+pub fn load(dir: &Path, blocks: &[String]) -> io::Result<Vec<Emoji>> {
+    let ranges = std::fs::read_to_string(dir.join(BLOCKS))?;
+    let symbols = block_symbols(&parse_blocks(&ranges), &characters(dir)?, blocks);
+    if symbols.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("no symbols found in {UNICODE_DATA}"),
+        ));
+    }
+    Ok(symbols)
+}
+
+fn block_symbols(
+    ranges: &[(&str, RangeInclusive<char>)],
+    characters: &[(char, String, bool)],
+    blocks: &[String],
+) -> Vec<Emoji> {
+    let mut symbols = Vec::new();
+    for block in blocks {
+        let Some((name, range)) = ranges
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(block))
+        else {
+            eprintln!("unknown Unicode block {block}");
+            continue;
+        };
+        let start = characters.partition_point(|(c, ..)| c < range.start());
+        let block_characters = characters[start..]
+            .iter()
+            .take_while(|(c, ..)| range.contains(c))
+            .filter(|(_, _, shown)| *shown);
+        for (c, char_name, _) in block_characters {
+            let symbol =
+                Emoji::text(Kind::Symbol, &c.to_string(), char_name, &[]).with_subgroup(name);
+            symbols.push(symbol);
+        }
+    }
+    symbols
+}
+
+/// Reads `UnicodeData.txt` in `dir`, unless it was already read from there.
+pub fn characters(dir: &Path) -> io::Result<Characters> {
+    if let Ok(cache) = UNICODE_DATA_CACHE.read() {
+        if let Some(cache) = cache.as_ref().filter(|cache| cache.dir == dir) {
+            return Ok(cache.characters.clone());
+        }
+    }
+    let contents = std::fs::read_to_string(dir.join(UNICODE_DATA))?;
+    let characters: Characters = parse_unicode_data(&contents)
+        .into_iter()
+        .map(|(c, name, category)| (c, name, !category.starts_with(['C', 'Z', 'M'])))
+        .collect();
+    if let Ok(mut cache) = UNICODE_DATA_CACHE.write() {
+        *cache = Some(UnicodeData {
+            dir: dir.to_path_buf(),
+            characters: characters.clone(),
+        });
+    }
+    Ok(characters)
+}
+
+/// Loads the symbols, unless they are already loaded from `dir` with the same `blocks`.
+pub fn init(dir: &str, blocks: &[String]) {
+    if is_loaded(dir, blocks) {
+        return;
+    }
+    let symbols = load(Path::new(dir), blocks).unwrap_or_else(|err| {
+        eprintln!("could not load Unicode symbols from {dir}: {err}");
+        Vec::new()
+    });
+    let Ok(mut loaded) = SYMBOLS.write() else {
+        eprintln!("failed to update symbols: lock is poisoned");
+        return;
+    };
+    *loaded = Some(Symbols {
+        dir: dir.to_string(),
+        blocks: blocks.to_vec(),
+        symbols: symbols.into_iter().map(data::intern).collect(),
+    });
+}
+
+/// Whether `init` ran with this configuration, also if loading failed.
+pub fn is_loaded(dir: &str, blocks: &[String]) -> bool {
+    SYMBOLS.read().is_ok_and(|loaded| {
+        loaded
+            .as_ref()
+            .is_some_and(|loaded| loaded.dir == dir && loaded.blocks == blocks)
+    })
+}
+
+/// The loaded symbols, empty until `init` succeeded.
+pub fn iter() -> impl Iterator<Item = &'static Emoji> {
+    let symbols = SYMBOLS
+        .read()
+        .ok()
+        .and_then(|loaded| loaded.as_ref().map(|loaded| loaded.symbols.clone()))
+        .unwrap_or_else(|| Arc::new([]));
+    (0..symbols.len()).map(move |i| symbols[i])
+}
+
+pub fn get(s: &str) -> Option<&'static Emoji> {
+    let loaded = SYMBOLS.read().ok()?;
+    let symbols = &loaded.as_ref()?.symbols;
+    symbols.iter().find(|symbol| symbol.as_str() == s).copied()
+}
+
+/// Parses lines like `2190..21FF; Arrows`.
+fn parse_blocks(contents: &str) -> Vec<(&str, RangeInclusive<char>)> {
+    contents
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let (range, name) = line.split_once(';')?;
+            let (start, end) = range.split_once("..")?;
+            let start = char::from_u32(u32::from_str_radix(start.trim(), 16).ok()?)?;
+            let end = char::from_u32(u32::from_str_radix(end.trim(), 16).ok()?)?;
+            Some((name.trim(), start..=end))
+        })
+        .collect()
+}
+
+/// Parses lines like `2192;RIGHTWARDS ARROW;Sm;...` into lowercase names and general categories,
+/// ordered by character.
+pub fn parse_unicode_data(contents: &str) -> Vec<(char, String, &str)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(';');
+            let (Some(codepoint), Some(name), Some(category)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return None;
+            };
+            // ranges like `<CJK Ideograph, First>` and controls have no name
+            if name.starts_with('<') {
+                return None;
+            }
+            let c = char::from_u32(u32::from_str_radix(codepoint, 16).ok()?)?;
+            Some((c, name.to_lowercase(), category))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCKS_FIXTURE: &str = "\
+# Blocks-15.1.0.txt
+0000..007F; Basic Latin
+0300..036F; Combining Diacritical Marks
+2190..21FF; Arrows
+";
+
+    const UNICODE_DATA_FIXTURE: &str = "\
+000A;<control>;Cc;0;B;;;;;N;LINE FEED (LF);;;;
+0020;SPACE;Zs;0;WS;;;;;N;;;;;
+0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;
+0301;COMBINING ACUTE ACCENT;Mn;230;NSM;;;;;N;NON-SPACING ACUTE;;;;
+2190;LEFTWARDS ARROW;Sm;0;ON;;;;;N;LEFT ARROW;;;;
+2192;RIGHTWARDS ARROW;Sm;0;ON;;;;;N;RIGHT ARROW;;;;
+4E00;<CJK Ideograph, First>;Lo;0;L;;;;;N;;;;;
+";
+
+    #[test]
+    fn blocks_are_parsed() {
+        let blocks = parse_blocks(BLOCKS_FIXTURE);
+        assert_eq!(
+            blocks,
+            [
+                ("Basic Latin", '\u{0}'..='\u{7f}'),
+                ("Combining Diacritical Marks", '\u{300}'..='\u{36f}'),
+                ("Arrows", '←'..='⇿'),
+            ]
+        );
+    }
+
+    #[test]
+    fn unicode_data_is_parsed() {
+        let characters = parse_unicode_data(UNICODE_DATA_FIXTURE);
+        // controls and ranges have no name
+        assert_eq!(
+            characters,
+            [
+                (' ', "space".to_string(), "Zs"),
+                ('A', "latin capital letter a".to_string(), "Lu"),
+                ('\u{301}', "combining acute accent".to_string(), "Mn"),
+                ('←', "leftwards arrow".to_string(), "Sm"),
+                ('→', "rightwards arrow".to_string(), "Sm"),
+            ]
+        );
+    }
+
+    #[test]
+    fn symbols_follow_the_blocks() {
+        let ranges = parse_blocks(BLOCKS_FIXTURE);
+        let characters: Vec<_> = parse_unicode_data(UNICODE_DATA_FIXTURE)
+            .into_iter()
+            .map(|(c, name, category)| (c, name, !category.starts_with(['C', 'Z', 'M'])))
+            .collect();
+        let blocks = [
+            "arrows",
+            "Combining Diacritical Marks",
+            "Unknown",
+            "Basic Latin",
+        ];
+        let blocks: Vec<_> = blocks.iter().map(|block| block.to_string()).collect();
+        let symbols: Vec<_> = block_symbols(&ranges, &characters, &blocks)
+            .iter()
+            .map(|symbol| (symbol.to_string(), symbol.subgroup().to_string()))
+            .collect();
+        // combining characters and spaces are left out
+        assert_eq!(
+            symbols,
+            [
+                ("←".to_string(), "Arrows".to_string()),
+                ("→".to_string(), "Arrows".to_string()),
+                ("A".to_string(), "Basic Latin".to_string()),
+            ]
+        );
+    }
+}
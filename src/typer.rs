@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: MPL-2.0
+// 2024 - Dominic Gerhauser and contributors
+
+use std::time::Duration;
+
+use crate::clipboard;
+
+/// Time for the compositor to return focus to the previous window after the popup is closed.
+const FOCUS_DELAY: Duration = Duration::from_millis(150);
+
+/// Types `text` into the focused window with a command such as `wtype {emoji}`,
+/// run with [`clipboard::run_command`].
+pub async fn insert(template: String, text: String) -> Result<(), String> {
+    tokio::time::sleep(FOCUS_DELAY).await;
+    clipboard::run_command(&template, &text, &[])
+        .await
+        .map_err(|err| format!("`{template}`: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::tests::{block_on, fake_command, recorded, temp_dir};
+    use std::fs;
+
+    #[test]
+    fn text_is_typed_as_argument_or_stdin() {
+        let dir = temp_dir("typer");
+        let typer = fake_command(&dir, "fake-type");
+
+        let template = format!("{} --delay 0 {}", typer.display(), clipboard::PLACEHOLDER);
+        assert_eq!(block_on(insert(template, "🦀".into())), Ok(()));
+        assert_eq!(recorded(&typer), ("--delay 0 🦀\n".into(), "".into()));
+
+        let template = typer.display().to_string();
+        assert_eq!(block_on(insert(template, "🦀".into())), Ok(()));
+        assert_eq!(recorded(&typer), ("\n".into(), "🦀".into()));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn failures_name_the_command() {
+        let err = block_on(insert("false {emoji}".into(), "🦀".into())).unwrap_err();
+        assert!(err.starts_with("`false {emoji}`: "), "{err}");
+
+        let err = block_on(insert("emoji-selector-missing".into(), "🦀".into())).unwrap_err();
+        assert!(err.contains("not found"), "{err}");
+    }
+}
@@ -2,17 +2,36 @@
 // 2024 - Dominic Gerhauser and contributors
 
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::iter;
+use std::sync::Arc;
 
+use crate::clipboard;
 use crate::config::Annotation;
 use crate::config::{Config, CONFIG_VERSION};
+use crate::data;
+use crate::emojify;
 #[allow(unused_imports)]
 use crate::fl;
+use crate::flag;
+use crate::font::{self, Unsupported};
+use crate::format::{self, CopyFormat, Presentation};
+use crate::hook;
+use crate::image;
+use crate::inspect;
+use crate::kaomoji;
+use crate::letters;
+use crate::localize::LANGUAGE_LOADER;
+use crate::pack;
+use crate::search;
+use crate::snippet;
+use crate::symbol;
+use crate::typer;
 use crate::widget_copy;
 use cosmic::app::Core;
 use cosmic::cosmic_config;
 use cosmic::iced;
+use cosmic::iced::keyboard::Modifiers;
 use cosmic::iced::wayland::popup::{destroy_popup, get_popup};
 use cosmic::iced::window::Id;
 #[allow(unused_imports)]
@@ -28,37 +47,129 @@ use cosmic_time::Timeline;
 use regex::RegexBuilder;
 pub const ID: &str = "dev.dominiccgeh.CosmicAppletEmojiSelector";
 const ICON: &str = ID;
+const GRID_SIZE: usize = 10;
+// kaomojis and other text entries need wider cells
+const TEXT_GRID_SIZE: usize = 3;
+const EMOJI_PADDING: u16 = 5;
+// emoji size and padding
+const ROW_HEIGHT: f32 = 35.0 + 2.0 * EMOJI_PADDING as f32;
+const SUBGROUP_HEIGHT: f32 = 32.0;
+const TOAST_DURATION: std::time::Duration = std::time::Duration::from_secs(2);
 pub struct Window {
     annotations: HashMap<String, Annotation>,
+    // sorted by the localized country name, as the Unicode order is by ISO code
+    flags: Vec<&'static data::Emoji>,
     core: Core,
     popup: Option<Id>,
     config: Config,
     #[allow(dead_code)]
     config_handler: Option<cosmic_config::Config>,
     timeline: Timeline,
-    selected_group: Option<emojis::Group>,
+    selected_group: Option<Group>,
     search: String,
+    // whether the search was empty before its last edit, see `Message::Backspace`
+    search_was_empty: bool,
+    query: search::Query,
     scrollable_id: widget::Id,
     font_family: cosmic::iced::font::Font,
-    emoji_hovered: Option<&'static emojis::Emoji>,
+    emoji_hovered: Option<&'static data::Emoji>,
     text_input_id: widget::Id,
+    modifiers: Modifiers,
+    compose: Vec<String>,
+    zwj: bool,
+    // the emojis which continue the composed ZWJ sequence
+    zwj_next: Option<Arc<HashSet<String>>>,
+    emoji_menu: Option<&'static data::Emoji>,
+    menu_presentation: Presentation,
+    toast: Option<String>,
+    toast_id: usize,
+    unsupported: Arc<HashSet<String>>,
+    collapsed_subgroups: HashSet<&'static str>,
+    current_subgroup: Option<usize>,
+    mode: Mode,
+    packs: Vec<pack::Pack>,
+    // built from the annotations when the emojify mode is first opened
+    emojify_index: Option<emojify::Index>,
+    emojified: Vec<emojify::Segment>,
+    inspected: Vec<inspect::Cluster>,
 }
 #[derive(Clone, Debug)]
 pub enum Message {
     Config(Config),
     TogglePopup,
     PopupClosed(Id),
-    Group(Option<emojis::Group>),
-    EmojiCopy(&'static emojis::Emoji),
+    Group(Option<Group>),
+    EmojiCopy(&'static data::Emoji),
+    EmojiCopyFormat(&'static data::Emoji, CopyFormat, Presentation),
+    EmojiMenu(Option<&'static data::Emoji>),
+    MenuPresentation(Presentation),
+    ToastExpired(usize),
+    PostCopy(Result<(), String>),
+    Unsupported(Arc<HashSet<String>>),
+    ToggleSubgroup(&'static str),
+    NextSubgroup,
+    PreviousSubgroup,
+    Mode(Mode),
+    CopyText(String),
+    EmojifyToggle(usize),
+    SymbolsLoaded,
+    NamesLoaded,
+    PacksChanged,
+    PacksLoaded(Vec<pack::Pack>),
+    EmojiCopyImage(&'static data::Emoji),
+    EmojiSaveImage(&'static data::Emoji),
+    ImageLoaded(
+        &'static data::Emoji,
+        String,
+        Result<clipboard::Data, String>,
+    ),
+    ImageSaved(Result<std::path::PathBuf, String>),
+    Copied(clipboard::Request, Result<(), String>),
+    EmojiInsert(&'static data::Emoji),
+    Inserted(String, Result<(), String>),
     Search(String),
     Frame(std::time::Instant),
-    EmojiHovered(&'static emojis::Emoji),
+    EmojiHovered(&'static data::Emoji),
     Exit,
     FocusTextInput,
     Enter,
     ArrowRight,
     ArrowLeft,
     ScrollToPercent(u8),
+    Modifiers(Modifiers),
+    // the Backspace key, and whether the search input captured it
+    Backspace(bool),
+    ComposeBackspace,
+    ComposeCopy,
+    ComposeZwj,
+    ComposeSkinTone(char),
+    ComposeHairStyle(char),
+}
+
+/// Whether emojis or the characters of the Unicode blocks in `Config::symbol_blocks` are shown,
+/// or emojis are generated from the search text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Emoji,
+    Symbol,
+    /// Letters and digits as emojis, see `letters::Style`
+    Generate,
+    /// Emojis suggested after the words of the search text
+    Emojify,
+    /// The characters of the search text, see `inspect::Cluster`
+    Inspect,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Group {
+    Emoji(emojis::Group),
+    Kaomoji,
+    /// `Config::snippets`
+    Custom,
+    /// Index into the loaded emoji packs
+    Pack(usize),
+    /// Index into `Config::collections`
+    Collection(usize),
 }
 
 #[derive(Clone, Debug)]
@@ -90,7 +201,7 @@ impl cosmic::Application for Window {
         let config = flags.config;
         let font_family =
             iced::Font::with_name(Box::leak(config.font_family.clone().into_boxed_str()));
-        let window = Window {
+        let mut window = Window {
             font_family,
             scrollable_id: widget::Id::unique(),
             selected_group,
@@ -99,13 +210,34 @@ impl cosmic::Application for Window {
             config_handler: flags.config_handler,
             popup: None,
             search: String::new(),
+            search_was_empty: true,
+            query: search::Query::default(),
             timeline: Timeline::new(),
             emoji_hovered: None,
             text_input_id: widget::Id::unique(),
             annotations: flags.annotations,
+            flags: Vec::new(),
+            modifiers: Modifiers::empty(),
+            compose: Vec::new(),
+            zwj: false,
+            zwj_next: None,
+            emoji_menu: None,
+            menu_presentation: Presentation::AsIs,
+            toast: None,
+            toast_id: 0,
+            unsupported: Arc::default(),
+            collapsed_subgroups: HashSet::new(),
+            current_subgroup: None,
+            mode: Mode::Emoji,
+            packs: Vec::new(),
+            emojify_index: None,
+            emojified: Vec::new(),
+            inspected: Vec::new(),
         };
+        window.flags = window.sorted_flags();
+        let command = Command::batch([window.check_unsupported(), window.load_packs()]);
 
-        (window, Command::none())
+        (window, command)
     }
 
     fn on_close_requested(&self, id: window::Id) -> Option<Message> {
@@ -137,6 +269,23 @@ impl cosmic::Application for Window {
             };
         }
 
+        // symbols have their own recently used list
+        macro_rules! remember_used {
+            ($emojis: expr) => {
+                if self.mode == Mode::Symbol {
+                    config_set!(
+                        last_used_symbols,
+                        self.last_used_with(&self.config.last_used_symbols, $emojis)
+                    )
+                } else {
+                    config_set!(
+                        last_used,
+                        self.last_used_with(&self.config.last_used, $emojis)
+                    )
+                }
+            };
+        }
+
         match message {
             Message::Config(config) => {
                 if config != self.config {
@@ -145,7 +294,32 @@ impl cosmic::Application for Window {
                             config.font_family.clone().into_boxed_str(),
                         ));
                     }
-                    self.config = config
+                    if let Some(Group::Collection(idx)) = self.selected_group {
+                        if idx >= config.collections.len() {
+                            self.selected_group = None;
+                        }
+                    }
+                    if config.snippets != self.config.snippets {
+                        snippet::set(&config.snippets);
+                        if config.snippets.is_empty() && self.selected_group == Some(Group::Custom)
+                        {
+                            self.selected_group = None;
+                        }
+                    }
+                    let check_unsupported = config.font_family != self.config.font_family
+                        || config.unsupported_emojis != self.config.unsupported_emojis;
+                    self.config = config;
+                    let mut commands = Vec::new();
+                    if check_unsupported {
+                        commands.push(self.check_unsupported());
+                    }
+                    // reloads the symbols if `ucd_dir` or `symbol_blocks` changed
+                    match self.mode {
+                        Mode::Symbol => commands.push(self.load_symbols()),
+                        Mode::Inspect => commands.push(self.load_names()),
+                        _ => {}
+                    }
+                    return Command::batch(commands);
                 }
             }
             Message::Frame(now) => self.timeline.now(now),
@@ -174,47 +348,332 @@ impl cosmic::Application for Window {
                 }
             }
             Message::EmojiCopy(emoji) => {
-                let mut last_used = self.config.last_used.clone();
-                if let Some(idx) = last_used.iter().position(|e| e == emoji.as_str()) {
-                    last_used.swap(0, idx);
+                if self.modifiers.shift() {
+                    self.compose_push(emoji.as_str());
+                    return Command::none();
+                }
+                if self.modifiers.control() {
+                    return self.update(Message::EmojiMenu(Some(emoji)));
+                }
+                if emoji.image().is_some() {
+                    return self.update(Message::EmojiCopyImage(emoji));
+                }
+                return self.update(Message::EmojiCopyFormat(
+                    emoji,
+                    self.config.copy_format,
+                    self.config.presentation,
+                ));
+            }
+            Message::EmojiCopyFormat(emoji, format, presentation) => {
+                self.emoji_menu = None;
+                remember_used!(&[emoji.as_str()]);
+                let text = format.format(emoji, presentation);
+                let name = self.emoji_name_localized(emoji).to_string();
+                let html = (format == CopyFormat::Emoji && !emoji.is_text())
+                    .then(|| format::html(&text, &name));
+                let contents = clipboard::Contents::Text {
+                    text: text.clone(),
+                    html,
+                };
+                return self.copy_and_close(contents, text, name);
+            }
+            Message::EmojiCopyImage(emoji) => {
+                self.emoji_menu = None;
+                let size = self.config.image_size;
+                let pack_copy = self.config.pack_copy;
+                let font_family = self.config.font_family.clone();
+                let text = match emoji.image() {
+                    Some(_) => emoji.to_string(),
+                    None => self.menu_presentation.apply(emoji.as_str()),
+                };
+                let load = {
+                    let text = text.clone();
+                    async move {
+                        tokio::task::spawn_blocking(move || match emoji.image() {
+                            Some(path) => pack::clipboard_data(path, pack_copy, size)
+                                .map_err(|err| format!("failed to read {}: {err}", path.display())),
+                            None => image::render_png(&text, &font_family, size)
+                                .map(|png_bytes| clipboard::Data::new("image/png", png_bytes))
+                                .map_err(|err| format!("failed to render emoji as image: {err}")),
+                        })
+                        .await
+                        .map_err(|err| err.to_string())
+                        .and_then(|res| res)
+                    }
+                };
+                return Command::perform(load, move |res| {
+                    cosmic::app::Message::App(Message::ImageLoaded(emoji, text, res))
+                });
+            }
+            Message::ImageLoaded(emoji, text, res) => match res {
+                Ok(data) => {
+                    remember_used!(&[emoji.as_str()]);
+                    let name = self.emoji_name_localized(emoji).to_string();
+                    let contents = clipboard::Contents::Data(data);
+                    return self.copy_and_close(contents, text, name);
+                }
+                Err(err) => eprintln!("{err}"),
+            },
+            Message::EmojiSaveImage(emoji) => {
+                self.emoji_menu = None;
+                let size = self.config.image_size;
+                let dir = if self.config.image_dir.is_empty() {
+                    image::default_dir()
                 } else {
-                    last_used.insert(0, emoji.to_string());
-                }
-                last_used.truncate(self.config.last_used_limit);
-                config_set!(last_used, last_used);
-                let mut commands = vec![iced::clipboard::write(emoji.to_string())];
-                if self.config.close_on_copy {
-                    if let Some(p) = self.popup.take() {
-                        commands.push(destroy_popup(p));
+                    self.config.image_dir.clone().into()
+                };
+                let font_family = self.config.font_family.clone();
+                let text = self.menu_presentation.apply(emoji.as_str());
+                let save = async move {
+                    tokio::task::spawn_blocking(move || match emoji.image() {
+                        Some(path) => image::save_file(&dir, path),
+                        None => image::render_png(&text, &font_family, size)
+                            .and_then(|png_bytes| image::save_png(&dir, emoji.name(), &png_bytes)),
+                    })
+                    .await
+                    .map_err(|err| err.to_string())
+                    .and_then(|res| res.map_err(|err| err.to_string()))
+                };
+                return Command::perform(save, |res| {
+                    cosmic::app::Message::App(Message::ImageSaved(res))
+                });
+            }
+            Message::ImageSaved(res) => {
+                if let Err(err) = res {
+                    eprintln!("failed to save emoji as image: {err}");
+                }
+            }
+            Message::EmojiMenu(emoji) => {
+                self.emoji_menu = emoji;
+                self.menu_presentation = match emoji {
+                    Some(emoji) if emoji.is_text() => Presentation::AsIs,
+                    _ => self.config.presentation,
+                };
+            }
+            Message::MenuPresentation(presentation) => self.menu_presentation = presentation,
+            Message::ToastExpired(id) => {
+                if id == self.toast_id {
+                    self.toast = None;
+                }
+            }
+            Message::Unsupported(unsupported) => self.unsupported = unsupported,
+            Message::ToggleSubgroup(subgroup) => {
+                if !self.collapsed_subgroups.remove(subgroup) {
+                    self.collapsed_subgroups.insert(subgroup);
+                }
+            }
+            Message::Mode(mode) => {
+                self.mode = mode;
+                self.emoji_hovered = None;
+                self.emoji_menu = None;
+                self.collapsed_subgroups.clear();
+                self.update_emojified();
+                self.update_inspected();
+                let mut commands = vec![self.update_group(None)];
+                match self.mode {
+                    Mode::Symbol => commands.push(self.load_symbols()),
+                    Mode::Inspect => commands.push(self.load_names()),
+                    _ => {}
+                }
+                return Command::batch(commands);
+            }
+            // the config may have changed while the symbols were loaded
+            Message::SymbolsLoaded if self.mode == Mode::Symbol => return self.load_symbols(),
+            // the symbols are read from `symbol::iter` when the view is updated
+            Message::SymbolsLoaded => {}
+            Message::NamesLoaded => self.update_inspected(),
+            Message::PacksChanged => return self.load_packs(),
+            Message::PacksLoaded(packs) => {
+                if let Some(Group::Pack(idx)) = self.selected_group {
+                    if idx >= packs.len() {
+                        self.selected_group = None;
                     }
                 }
+                self.packs = packs;
+            }
+            Message::NextSubgroup => return self.jump_subgroup(true),
+            Message::PreviousSubgroup => return self.jump_subgroup(false),
+            Message::PostCopy(res) => {
+                if let Err(err) = res {
+                    eprintln!("failed to run post-copy command: {err}");
+                }
+            }
+            Message::Backspace(captured) => {
+                // the search input handles the press first, so it is only passed on to the
+                // compose buffer once there is nothing left to delete in the search
+                if self.mode == Mode::Emoji && (!captured || self.search_was_empty) {
+                    return self.update(Message::ComposeBackspace);
+                }
+            }
+            Message::ComposeBackspace => {
+                self.compose.pop();
+                self.update_zwj_next();
+            }
+            Message::ComposeZwj => {
+                self.zwj = !self.zwj;
+                self.update_zwj_next();
+            }
+            Message::ComposeSkinTone(skin_tone) => {
+                if let Some(last) = self.compose.last_mut() {
+                    *last = data::with_skin_tone(last, skin_tone);
+                }
+                self.update_zwj_next();
+            }
+            Message::ComposeHairStyle(hair_style) => self.compose_push(&hair_style.to_string()),
+            Message::ComposeCopy => {
+                if self.compose.is_empty() {
+                    return Command::none();
+                }
+                if self.zwj {
+                    let text = self.compose_text();
+                    self.compose.clear();
+                    self.update_zwj_next();
+                    // other sequences could not be looked up in the recently used emojis
+                    if data::is_rgi(&text) {
+                        remember_used!(&[text.as_str()]);
+                    }
+                    let name = match data::get(&text) {
+                        Some(emoji) => self.emoji_name_localized(emoji).to_string(),
+                        None => text.clone(),
+                    };
+                    let contents = clipboard::Contents::Text {
+                        text: text.clone(),
+                        html: Some(format::html(&text, &name)),
+                    };
+                    return self.copy_and_close(contents, text, name);
+                }
+                let compose = std::mem::take(&mut self.compose);
+                let emojis: Vec<_> = compose.iter().map(String::as_str).collect();
+                remember_used!(&emojis);
+                let presentation = self.config.presentation;
+                let text: String = compose.iter().map(|e| presentation.apply(e)).collect();
+                let name = compose
+                    .iter()
+                    .map(|e| data::get(e).map_or(e.as_str(), |e| self.emoji_name_localized(e)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let html = compose
+                    .iter()
+                    .map(|e| match data::get(e) {
+                        Some(emoji) => {
+                            format::html(&presentation.apply(e), self.emoji_name_localized(emoji))
+                        }
+                        None => presentation.apply(e),
+                    })
+                    .collect();
+                let contents = clipboard::Contents::Text {
+                    text: text.clone(),
+                    html: Some(html),
+                };
+                return self.copy_and_close(contents, text, name);
+            }
+            Message::Copied(request, res) => {
+                if let Err(err) = &res {
+                    eprintln!("failed to copy with {err}, trying next backend");
+                }
+                return self.copy(request);
+            }
+            // images cannot be typed
+            Message::EmojiInsert(emoji) if emoji.image().is_some() => {
+                return self.update(Message::EmojiCopyImage(emoji));
+            }
+            Message::EmojiInsert(emoji) => {
+                remember_used!(&[emoji.as_str()]);
+                let text = CopyFormat::Emoji.format(emoji, self.config.presentation);
+                // the popup has to be closed, so that the previous window is focused again
+                let mut commands = Vec::with_capacity(2);
+                if let Some(p) = self.popup.take() {
+                    commands.push(destroy_popup(p));
+                }
+                let insert = typer::insert(self.config.insert_command.clone(), text.clone());
+                commands.push(Command::perform(insert, move |res| {
+                    cosmic::app::Message::App(Message::Inserted(text, res))
+                }));
                 return Command::batch(commands);
             }
+            Message::Inserted(text, res) => {
+                if let Err(err) = res {
+                    eprintln!("failed to insert emoji, copying instead: {err}");
+                    // the popup is already closed, so the iced clipboard would be lost
+                    let target = self.config.copy_target;
+                    let backends = self.config.external_clipboard_backends();
+                    let contents = clipboard::Contents::Text { text, html: None };
+                    return self.copy(clipboard::Request::new(contents, target, backends, false));
+                }
+            }
             Message::Search(search) => {
+                self.search_was_empty = self.search.is_empty();
+                self.query = search::Query::parse(&search);
                 self.search = search;
+                self.current_subgroup = None;
                 self.emoji_hovered = None;
+                self.update_emojified();
+                self.update_inspected();
             }
             Message::Group(group) => return self.update_group(group),
 
             Message::EmojiHovered(emoji) => self.emoji_hovered = Some(emoji),
             Message::Exit => {
+                if self.emoji_menu.take().is_some() {
+                    return Command::none();
+                }
                 if let Some(p) = self.popup.take() {
                     return destroy_popup(p);
                 }
             }
-            Message::Enter => {}
+            Message::CopyText(text) => {
+                if text.is_empty() {
+                    return Command::none();
+                }
+                let contents = clipboard::Contents::Text {
+                    text: text.clone(),
+                    html: None,
+                };
+                return self.copy_and_close(contents, text, String::new());
+            }
+            Message::EmojifyToggle(idx) => {
+                if let Some(segment) = self.emojified.get_mut(idx) {
+                    segment.accepted = !segment.accepted;
+                }
+            }
+            Message::Enter => {
+                if self.mode == Mode::Generate {
+                    let text = letters::Style::ALL[0].generate(&self.search);
+                    return self.update(Message::CopyText(text));
+                }
+                if self.mode == Mode::Emojify {
+                    let text = emojify::render(&self.emojified);
+                    return self.update(Message::CopyText(text));
+                }
+                // the characters are copied one by one
+                if self.mode == Mode::Inspect {
+                    return Command::none();
+                }
+                if self.modifiers.control() {
+                    if let Some(emoji) = self.selected_emoji() {
+                        self.compose_push(emoji.as_str());
+                    }
+                } else if !self.compose.is_empty() {
+                    return self.update(Message::ComposeCopy);
+                } else if let Some(emoji) = self.selected_emoji() {
+                    return self.update(Message::EmojiCopy(emoji));
+                }
+            }
+            Message::Modifiers(modifiers) => self.modifiers = modifiers,
             Message::FocusTextInput => {
                 return widget::text_input::focus(self.text_input_id.clone());
             }
             Message::ArrowRight => {
-                let mut key = key_from_group(self.selected_group);
-                key = if key >= b'9' { b'0' } else { key + 1 };
-                return self.update_group(group_from_key(key));
+                let groups = self.groups_cycle();
+                let idx = group_position(&groups, self.selected_group);
+                let idx = if idx + 1 >= groups.len() { 0 } else { idx + 1 };
+                return self.update_group(groups[idx]);
             }
             Message::ArrowLeft => {
-                let mut key = key_from_group(self.selected_group);
-                key = if key <= b'0' { b'9' } else { key - 1 };
-                return self.update_group(group_from_key(key));
+                let groups = self.groups_cycle();
+                let idx = group_position(&groups, self.selected_group);
+                let idx = if idx == 0 { groups.len() - 1 } else { idx - 1 };
+                return self.update_group(groups[idx]);
             }
             Message::ScrollToPercent(percent) => {
                 let offset = if percent == 0 {
@@ -237,13 +696,7 @@ impl cosmic::Application for Window {
     }
     // todo extract more code into functions
     fn view_window(&self, _id: Id) -> Element<Self::Message> {
-        // use regex to apply simple unicode case folding
-        let regex_pattern = regex::escape(&self.search);
-        let search_regex = RegexBuilder::new(&regex_pattern)
-            .case_insensitive(true)
-            .build()
-            .ok();
-
+        let search_regex = self.search_regex();
         let search_filter = self.search_filter();
 
         #[allow(unused_variables)]
@@ -259,17 +712,17 @@ impl cosmic::Application for Window {
             space_xxl,  // 64
             space_xxxl, // 128
         } = self.core.system_theme().cosmic().spacing;
-        let mut content = widget::column::with_capacity(6)
+        let mut content = widget::column::with_capacity(7)
             .padding([space_xxs, space_xxxs])
             .spacing(space_m);
 
-        let mut groups = widget::row::with_capacity(9).width(Length::Fill);
+        let mut groups =
+            widget::row::with_capacity(11 + self.config.collections.len()).width(Length::Fill);
 
-        for group in emojis::Group::iter() {
+        for group in self.groups() {
             let is_selected = self.selected_group.is_some_and(|sel| sel == group);
-            let group_btn = widget::icon::from_name(group_icon(group))
-                .symbolic(true)
-                .size(space_m)
+            let group_btn = self
+                .group_icon(group, space_m)
                 .apply(widget::button)
                 // honestly there isnt a good style
                 // needs containers
@@ -283,18 +736,59 @@ impl cosmic::Application for Window {
 
             groups = groups.push(group_btn);
         }
-        content = content.push(groups);
+        // symbols are sectioned by block instead
+        if self.mode == Mode::Emoji {
+            content = content.push(groups);
+        }
 
-        let search = widget::search_input(fl!("search-for-emojis"), &self.search)
+        let placeholder = match self.mode {
+            Mode::Emoji => fl!("search-for-emojis"),
+            Mode::Symbol => fl!("search-for-symbols"),
+            Mode::Generate => fl!("type-text"),
+            Mode::Emojify | Mode::Inspect => fl!("paste-text"),
+        };
+        let search = widget::search_input(placeholder, &self.search)
             .on_input(Message::Search)
             .on_paste(Message::Search)
             .on_clear(Message::Search(String::new()))
             .id(self.text_input_id.clone())
             .on_submit(Message::Enter)
             .width(Length::Fill);
+        let mut search = widget::row::with_capacity(5)
+            .spacing(space_xxs)
+            .align_items(Alignment::Center)
+            .push(search);
+        for (mode, icon) in [
+            (Mode::Symbol, "accessories-character-map-symbolic"),
+            (Mode::Generate, "format-text-bold-symbolic"),
+            (Mode::Emojify, "tools-check-spelling-symbolic"),
+            (Mode::Inspect, "dialog-information-symbolic"),
+        ] {
+            let is_selected = self.mode == mode;
+            let mode_btn = widget::icon::from_name(icon)
+                .size(space_s)
+                .apply(widget::button)
+                .style(cosmic::theme::Button::Icon)
+                .selected(is_selected)
+                .padding(space_xxs)
+                .on_press(Message::Mode(if is_selected { Mode::Emoji } else { mode }));
+            search = search.push(mode_btn);
+        }
         content = content.push(search);
 
-        if self.config.show_preview {
+        if let Some(toast) = &self.toast {
+            let toast_emoji = widget::text(toast.as_str())
+                .font(self.font_family)
+                .shaping(cosmic::iced_core::text::Shaping::Advanced)
+                .size(35);
+            let toast = widget::row::with_capacity(2)
+                .spacing(space_xxs)
+                .align_items(Alignment::Center)
+                .push(widget::text::title4(fl!("copied")))
+                .push(toast_emoji);
+            let toast_container = widget::container(toast).center_y().height(65);
+            content = content.push(toast_container);
+        } else if self.config.show_preview && matches!(self.mode, Mode::Emoji | Mode::Symbol) {
             let preview = self.preview(
                 &search_filter,
                 &search_regex,
@@ -304,45 +798,147 @@ impl cosmic::Application for Window {
             content = content.push(preview_container);
         }
 
-        const GRID_SIZE: usize = 10;
+        if !self.compose.is_empty() {
+            let compose_text = widget::text(self.compose_text())
+                .font(self.font_family)
+                .shaping(cosmic::iced_core::text::Shaping::Advanced)
+                .size(25)
+                .width(Length::Fill);
+            let backspace_btn = widget::icon::from_name("edit-clear-symbolic")
+                .size(space_s)
+                .apply(widget::button)
+                .style(cosmic::theme::Button::Icon)
+                .padding(space_xxs)
+                .on_press(Message::ComposeBackspace);
+            let copy_btn = widget::button(widget::text(fl!("copy")))
+                .style(cosmic::theme::Button::Suggested)
+                .on_press(Message::ComposeCopy);
+            let zwj_btn = widget::button(widget::text(fl!("zwj-join")))
+                .style(if self.zwj {
+                    cosmic::theme::Button::Suggested
+                } else {
+                    cosmic::theme::Button::Standard
+                })
+                .on_press(Message::ComposeZwj);
+            let compose = widget::row::with_capacity(4)
+                .spacing(space_xxs)
+                .align_items(Alignment::Center)
+                .push(compose_text)
+                .push(zwj_btn)
+                .push(backspace_btn)
+                .push(copy_btn);
+            content = content.push(compose);
+            if self.zwj {
+                content = content.push(self.zwj_composer(space_xxs));
+            }
+        }
+
+        // the other modes still show the toast and the compose buffer
+        if self.mode == Mode::Generate {
+            content = content.push(self.generated(space_xxs));
+            return self.core.applet.popup_container(content).into();
+        }
+        if self.mode == Mode::Emojify {
+            content = content.push(self.emojify_view(space_xxs));
+            return self.core.applet.popup_container(content).into();
+        }
+        if self.mode == Mode::Inspect {
+            content = content.push(self.inspect_view(space_xxs));
+            return self.core.applet.popup_container(content).into();
+        }
+
+        if let Some(emoji) = self.emoji_menu {
+            content = content.push(self.emoji_menu(emoji, space_xxs));
+        }
 
         let mut grid = widget::column();
 
-        let emoji_row = |emojis: [Option<&'static emojis::Emoji>; 10]| {
-            let mut row = widget::row::with_capacity(GRID_SIZE);
-            for emoji in emojis.iter().filter_map(|e| *e) {
-                // todo figure out button and text style
-                let emoji_txt = widget::text(emoji.to_string())
-                    .size(25)
-                    .width(35)
-                    .height(35)
-                    .font(self.font_family)
-                    .shaping(cosmic::iced_core::text::Shaping::Advanced)
-                    .horizontal_alignment(alignment::Horizontal::Center)
-                    .vertical_alignment(alignment::Vertical::Center);
-                let mut emoji_btn = widget::button(emoji_txt)
-                    .on_press(Message::EmojiCopy(emoji))
-                    .style(cosmic::theme::Button::Icon)
-                    .apply(widget_copy::MouseArea::new)
-                    .on_enter(Message::EmojiHovered(emoji))
-                    .apply(Element::from);
-
-                if self.config.show_tooltip {
-                    let tooltip = format_emoji(&emoji, self.config.show_unicode);
-                    let emoji_tooltip =
-                        widget::tooltip(emoji_btn, tooltip, widget::tooltip::Position::Top);
-                    emoji_btn = emoji_tooltip.into()
-                }
-                row = row.push(emoji_btn)
-            }
-            row
+        let emoji_cell = |emoji: &'static data::Emoji| {
+            // todo figure out button and text style
+            let emoji_txt = widget::text(emoji.to_string());
+            let mut emoji_txt = if emoji.is_wide() {
+                emoji_txt.size(16).width(Length::Fill)
+            } else if emoji.is_text() {
+                emoji_txt.size(25).width(35)
+            } else {
+                emoji_txt.size(25).width(35).font(self.font_family)
+            }
+            .height(35)
+            .shaping(cosmic::iced_core::text::Shaping::Advanced)
+            .horizontal_alignment(alignment::Horizontal::Center)
+            .vertical_alignment(alignment::Vertical::Center);
+            // missing glyphs are drawn with the text color, unlike color emojis
+            if self.config.unsupported_emojis == Unsupported::Dim
+                && self.unsupported.contains(emoji.as_str())
+            {
+                emoji_txt = emoji_txt.style(cosmic::theme::Text::Color(iced::Color::from_rgba(
+                    0.5, 0.5, 0.5, 0.4,
+                )));
+            }
+            let emoji_content: Element<Message> = match emoji.image() {
+                Some(image) => widget::icon(widget::icon::from_path(image.to_path_buf()))
+                    .size(35)
+                    .into(),
+                None => emoji_txt.into(),
+            };
+            let (on_press, on_middle_press) = if self.config.insert_on_click {
+                (Message::EmojiInsert(emoji), Message::EmojiCopy(emoji))
+            } else {
+                (Message::EmojiCopy(emoji), Message::EmojiInsert(emoji))
+            };
+            let mut emoji_btn = widget::button(emoji_content)
+                .on_press(on_press)
+                .style(cosmic::theme::Button::Icon)
+                .padding(EMOJI_PADDING);
+            if emoji.is_wide() {
+                emoji_btn = emoji_btn.width(Length::Fill);
+            }
+            let mut emoji_btn = emoji_btn
+                .apply(widget_copy::MouseArea::new)
+                .on_middle_press(on_middle_press)
+                .on_right_press(Message::EmojiMenu(Some(emoji)))
+                .on_enter(Message::EmojiHovered(emoji))
+                .apply(Element::from);
+
+            if self.config.show_tooltip {
+                let tooltip = format_emoji(&emoji, self.config.show_unicode);
+                let emoji_tooltip =
+                    widget::tooltip(emoji_btn, tooltip, widget::tooltip::Position::Top);
+                emoji_btn = emoji_tooltip.into()
+            }
+            emoji_btn
+        };
+
+        // emojis in a grid, followed by the wider text entries
+        let emoji_rows = |emojis: Vec<&'static data::Emoji>| {
+            let (texts, emojis): (Vec<_>, Vec<_>) = emojis.into_iter().partition(|e| e.is_wide());
+            let mut rows: Vec<Element<Message>> = Vec::new();
+            for emojis in chunks::<_, GRID_SIZE>(emojis.into_iter()) {
+                // fixed height, so that the offset of subgroups is known
+                let mut row = widget::row::with_capacity(GRID_SIZE).height(ROW_HEIGHT);
+                for emoji in emojis.into_iter().flatten() {
+                    row = row.push(emoji_cell(emoji));
+                }
+                rows.push(row.into());
+            }
+            for texts in chunks::<_, TEXT_GRID_SIZE>(texts.into_iter()) {
+                let mut row = widget::row::with_capacity(TEXT_GRID_SIZE)
+                    .width(Length::Fill)
+                    .height(ROW_HEIGHT);
+                for text in texts.into_iter().flatten() {
+                    row = row.push(emoji_cell(text));
+                }
+                rows.push(row.into());
+            }
+            rows
         };
 
-        let search_iter = self.config_emoji_iter(&search_filter, &search_regex);
-        let mut has_favorite = false;
-        for emojis in chunks(search_iter) {
-            has_favorite = true;
-            grid = grid.push(emoji_row(emojis));
+        let favorites: Vec<_> = self
+            .config_emoji_iter(&search_filter, &search_regex)
+            .collect();
+        let has_favorite = !favorites.is_empty();
+        for row in emoji_rows(favorites) {
+            grid = grid.push(row);
         }
         if has_favorite {
             grid = grid.push(widget::vertical_space(space_xs));
@@ -351,8 +947,22 @@ impl cosmic::Application for Window {
         }
 
         let emoji_iter = self.emoji_iter(search_filter, &search_regex);
-        for emojis in chunks(emoji_iter) {
-            grid = grid.push(emoji_row(emojis));
+        if self.show_subgroups() {
+            for (subgroup, emojis) in subgroups(emoji_iter) {
+                if !subgroup.is_empty() {
+                    grid = grid.push(self.subgroup_header(subgroup, space_xxs));
+                }
+                if self.collapsed_subgroups.contains(subgroup) {
+                    continue;
+                }
+                for row in emoji_rows(emojis) {
+                    grid = grid.push(row);
+                }
+            }
+        } else {
+            for row in emoji_rows(emoji_iter.collect()) {
+                grid = grid.push(row);
+            }
         }
         let grid = grid
             .apply(widget::container)
@@ -388,7 +998,9 @@ impl cosmic::Application for Window {
             .as_subscription()
             .map(|(_, now)| Message::Frame(now));
 
-        Subscription::batch(vec![config, timeline, navigation_subscription()])
+        let packs = pack::subscription(Message::PacksChanged);
+
+        Subscription::batch(vec![config, timeline, packs, navigation_subscription()])
     }
 
     fn style(&self) -> Option<<Theme as application::StyleSheet>::Style> {
@@ -402,66 +1014,117 @@ impl Window {
         &'a self,
         search_filter: S,
         search_regex: &'b Option<regex::Regex>,
-    ) -> impl Iterator<Item = &'static emojis::Emoji> + 'b
+    ) -> impl Iterator<Item = &'static data::Emoji> + 'b
     where
-        S: for<'c> Fn(&'static emojis::Emoji, Option<&'c regex::Regex>) -> bool + 'b,
+        S: for<'c> Fn(&'static data::Emoji, Option<&'c regex::Regex>) -> bool + 'b,
     {
         let selected_group = self.selected_group;
-        let search_iter = self
-            .config
-            .last_used
+        let collection = self.collection_emojis();
+        let pack = self.pack_emojis();
+        let is_visible = self.visible_filter();
+        let (last_used, get): (_, fn(&str) -> Option<&'static data::Emoji>) = match self.mode {
+            Mode::Symbol => (&self.config.last_used_symbols, symbol::get),
+            _ => (&self.config.last_used, data::get),
+        };
+        let search_iter = last_used
             .clone()
             .into_iter()
-            .filter_map(|e| emojis::get(&e))
-            .filter(move |e| selected_group.is_none() || Some(e.group()) == selected_group)
+            .filter_map(move |e| get(&e))
+            .filter(move |e| match selected_group {
+                None => true,
+                Some(Group::Emoji(group)) => e.group() == Some(group),
+                Some(Group::Kaomoji) => e.kind() == data::Kind::Kaomoji,
+                Some(Group::Custom) => e.kind() == data::Kind::Custom,
+                Some(Group::Pack(_)) => pack.contains(e),
+                Some(Group::Collection(_)) => collection.iter().any(|c| c == e.as_str()),
+            })
+            .filter(move |e| is_visible(e))
             .filter(move |emoji| search_filter(emoji, search_regex.as_ref()));
         search_iter
     }
+
+    // the annotations are loaded once on startup, so the flags are only sorted then
+    fn sorted_flags(&self) -> Vec<&'static data::Emoji> {
+        let mut flags: Vec<_> = data::group_emojis(emojis::Group::Flags).collect();
+        flags.sort_by_cached_key(|emoji| {
+            let name = flag::sort_key(self.emoji_name_localized(emoji));
+            (flag::kind(emoji.as_str()), name)
+        });
+        flags
+    }
+
     fn emoji_iter<'a, 'b, S>(
         &'a self,
         search_filter: S,
         search_regex: &'b Option<regex::Regex>,
-    ) -> impl Iterator<Item = &'static emojis::Emoji> + 'b
+    ) -> impl Iterator<Item = &'static data::Emoji> + 'b
     where
-        S: for<'c> Fn(&'static emojis::Emoji, Option<&'c regex::Regex>) -> bool + 'b,
+        S: for<'c> Fn(&'static data::Emoji, Option<&'c regex::Regex>) -> bool + 'b,
     {
-        let emoji_iter: Box<dyn Iterator<Item = &'static emojis::Emoji>> = match self.selected_group
-        {
-            Some(group) => Box::from(group.emojis()),
-            None => Box::from(emojis::iter()),
+        let emoji_iter: Box<dyn Iterator<Item = &'static data::Emoji>> = match self.selected_group {
+            _ if self.mode == Mode::Symbol => Box::from(symbol::iter()),
+            Some(Group::Emoji(emojis::Group::Flags)) => Box::from(self.flags.clone().into_iter()),
+            Some(Group::Emoji(group)) => Box::from(data::group_emojis(group)),
+            Some(Group::Kaomoji) => Box::from(kaomoji::iter()),
+            Some(Group::Custom) => Box::from(snippet::iter()),
+            Some(Group::Pack(_)) => Box::from(self.pack_emojis().into_iter()),
+            Some(Group::Collection(_)) => Box::from(
+                self.collection_emojis()
+                    .into_iter()
+                    .filter_map(|e| data::get(&e)),
+            ),
+            // kaomojis, snippets and packs only show up in the search results
+            None if self.query.text.is_empty() => Box::from(data::iter()),
+            None => {
+                let packs: Vec<_> = self
+                    .packs
+                    .iter()
+                    .flat_map(|pack| pack.emojis.clone())
+                    .collect();
+                Box::from(
+                    data::iter()
+                        .chain(kaomoji::iter())
+                        .chain(snippet::iter())
+                        .chain(packs),
+                )
+            }
         };
-        emoji_iter.filter(move |emoji| search_filter(emoji, search_regex.as_ref()))
+        let is_visible = self.visible_filter();
+        emoji_iter
+            .filter(move |emoji| is_visible(emoji))
+            .filter(move |emoji| search_filter(emoji, search_regex.as_ref()))
     }
     fn preview(
         &self,
-        search_filter: impl Fn(&'static emojis::Emoji, Option<&regex::Regex>) -> bool,
+        search_filter: impl Fn(&'static data::Emoji, Option<&regex::Regex>) -> bool,
         search_regex: &Option<regex::Regex>,
         spacing: &cosmic::cosmic_theme::Spacing,
     ) -> Element<Message> {
-        let favorites_first = || self.config_emoji_iter(&search_filter, &search_regex).next();
-        let emojis_first = || {
-            (!self.search.is_empty()).then_some(())?;
-            self.emoji_iter(&search_filter, &search_regex).next()
-        };
-        let preview = if let Some(emoji_hovered) = self
-            .emoji_hovered
-            .or_else(favorites_first)
-            .or_else(emojis_first)
-        {
+        let preview = if let Some(emoji_hovered) = self.first_emoji(&search_filter, search_regex) {
             let mut preview = widget::row::with_capacity(2)
                 .spacing(spacing.space_xxs)
                 .align_items(Alignment::Center);
             // todo size and width is arbitary; user config?
 
-            let preview_emoji = widget::text(emoji_hovered.as_str())
-                .font(self.font_family)
-                .shaping(cosmic::iced_core::text::Shaping::Advanced)
-                .size(35)
-                .height(50)
-                .width(50)
-                .horizontal_alignment(alignment::Horizontal::Center)
-                .vertical_alignment(alignment::Vertical::Center);
-            preview = preview.push(preview_emoji);
+            let preview_emoji = widget::text(emoji_hovered.as_str());
+            let preview_emoji = if emoji_hovered.is_wide() {
+                preview_emoji.size(20)
+            } else if emoji_hovered.is_text() {
+                preview_emoji.size(35).width(50)
+            } else {
+                preview_emoji.font(self.font_family).size(35).width(50)
+            }
+            .shaping(cosmic::iced_core::text::Shaping::Advanced)
+            .height(50)
+            .horizontal_alignment(alignment::Horizontal::Center)
+            .vertical_alignment(alignment::Vertical::Center);
+            preview = match emoji_hovered.image() {
+                Some(image) => {
+                    let preview_image = widget::icon(widget::icon::from_path(image.to_path_buf()));
+                    preview.push(preview_image.size(50))
+                }
+                None => preview.push(preview_emoji),
+            };
             let show_unicode = self.config.show_unicode;
             let mut right_preview = widget::column::with_capacity(2 + show_unicode as usize);
 
@@ -483,30 +1146,127 @@ impl Window {
             let preview_name = widget::text::title4(emoji_name);
             right_preview = right_preview.push(preview_name);
 
+            let mut details = widget::row::with_capacity(2)
+                .spacing(spacing.space_xxs)
+                .align_items(Alignment::Center);
             if let Some(shortcode) = emoji_hovered.shortcode() {
-                right_preview = right_preview.push(widget::text::body(shortcode))
+                details = details.push(widget::text::body(shortcode))
             }
+            if let Some(version) = emoji_hovered.unicode_version() {
+                let version_badge =
+                    widget::text::caption(format!("E{}.{}", version.major(), version.minor()))
+                        .apply(widget::container)
+                        .padding([0, spacing.space_xxs])
+                        .style(cosmic::theme::Container::Card);
+                details = details.push(version_badge);
+            }
+            right_preview = right_preview.push(details);
             if show_unicode {
-                let unicode_chars = emoji_hovered
-                    .as_str()
-                    .chars()
-                    .map(|c| format!("U+{:X}", c as u32))
-                    .collect::<Vec<_>>()
-                    .join(" ");
+                let unicode_chars = format::codepoints(emoji_hovered.as_str());
                 right_preview = right_preview.push(widget::text::caption(unicode_chars));
             }
 
             preview = preview.push(right_preview);
             preview.apply(Element::from)
         } else if let Some(group) = self.selected_group {
-            widget::text::title1(group_string(group)).into()
+            widget::text::title1(self.group_string(group)).into()
+        } else if self.mode == Mode::Symbol {
+            widget::text::title1(fl!("unicode-symbols")).into()
         } else {
             widget::text::title1(fl!("emojis-and-favorites")).into()
         };
         return preview;
     }
 
-    fn emoji_name_localized<'a>(&'a self, emoji_hovered: &'static emojis::Emoji) -> &'a str {
+    // copy the emoji in another format
+    fn emoji_menu(&self, emoji: &'static data::Emoji, spacing: u16) -> Element<Message> {
+        let mut menu = widget::column::with_capacity(CopyFormat::ALL.len() + 3);
+        // only offered for emojis with a variation sequence
+        let emoji_str = emoji.as_str();
+        if !emoji.is_text()
+            && Presentation::Emoji.apply(emoji_str) != Presentation::Text.apply(emoji_str)
+        {
+            let mut presentations = widget::row::with_capacity(Presentation::ALL.len())
+                .spacing(spacing)
+                .width(Length::Fill);
+            for presentation in Presentation::ALL {
+                let style = if presentation == self.menu_presentation {
+                    cosmic::theme::Button::Suggested
+                } else {
+                    cosmic::theme::Button::Standard
+                };
+                let btn = widget::button(widget::text(presentation.name()))
+                    .style(style)
+                    .on_press(Message::MenuPresentation(presentation));
+                presentations = presentations.push(btn);
+            }
+            menu = menu.push(presentations);
+        }
+        // images are only copied as a whole
+        let formats = if emoji.image().is_some() {
+            &[][..]
+        } else {
+            &CopyFormat::ALL[..]
+        };
+        for &format in formats {
+            let mut value = widget::text(format.format(emoji, self.menu_presentation));
+            if format == CopyFormat::Emoji && !emoji.is_text() {
+                value = value
+                    .font(self.font_family)
+                    .shaping(cosmic::iced_core::text::Shaping::Advanced);
+            }
+            let item = widget::row::with_capacity(2)
+                .spacing(spacing)
+                .align_items(Alignment::Center)
+                .push(widget::text(format.name()).width(Length::Fill))
+                .push(value)
+                .apply(widget::button)
+                .style(cosmic::theme::Button::Text)
+                .width(Length::Fill)
+                .on_press(Message::EmojiCopyFormat(
+                    emoji,
+                    format,
+                    self.menu_presentation,
+                ));
+            menu = menu.push(item);
+        }
+        for (name, message) in [
+            (fl!("copy-image"), Message::EmojiCopyImage(emoji)),
+            (fl!("save-image"), Message::EmojiSaveImage(emoji)),
+        ] {
+            let item = widget::text(name)
+                .width(Length::Fill)
+                .apply(widget::button)
+                .style(cosmic::theme::Button::Text)
+                .width(Length::Fill)
+                .on_press(message);
+            menu = menu.push(item);
+        }
+        menu.into()
+    }
+
+    // the hovered emoji, otherwise the first favorite or search result
+    fn first_emoji(
+        &self,
+        search_filter: impl Fn(&'static data::Emoji, Option<&regex::Regex>) -> bool,
+        search_regex: &Option<regex::Regex>,
+    ) -> Option<&'static data::Emoji> {
+        let favorites_first = || self.config_emoji_iter(&search_filter, &search_regex).next();
+        let emojis_first = || {
+            (!self.search.is_empty()).then_some(())?;
+            self.emoji_iter(&search_filter, &search_regex).next()
+        };
+        self.emoji_hovered
+            .or_else(favorites_first)
+            .or_else(emojis_first)
+    }
+
+    fn selected_emoji(&self) -> Option<&'static data::Emoji> {
+        let search_regex = self.search_regex();
+        self.first_emoji(self.search_filter(), &search_regex)
+    }
+
+    fn emoji_name_localized<'a>(&'a self, emoji_hovered: &'static data::Emoji) -> &'a str {
         let emoji_name = self
             .annotations
             .get(
@@ -519,30 +1279,617 @@ impl Window {
         emoji_name
     }
 
-    fn update_group(
-        &mut self,
-        group: Option<emojis::Group>,
-    ) -> Command<cosmic::app::Message<Message>> {
+    fn update_group(&mut self, group: Option<Group>) -> Command<cosmic::app::Message<Message>> {
         self.emoji_hovered = None;
-        self.selected_group = group;
+        // symbols have no groups
+        self.selected_group = group.filter(|_| self.mode == Mode::Emoji);
+        self.current_subgroup = None;
         return scrollable::scroll_to(
             self.scrollable_id.clone(),
             scrollable::AbsoluteOffset::default(),
         );
     }
 
+    fn compose_push(&mut self, emoji: &str) {
+        self.compose.push(emoji.to_string());
+        self.update_zwj_next();
+    }
+
+    // the composed emojis, joined into one sequence in the ZWJ composer
+    fn compose_text(&self) -> String {
+        if self.zwj {
+            self.compose.join(&data::ZWJ.to_string())
+        } else {
+            self.compose.concat()
+        }
+    }
+
+    // only the emojis that continue a recommended sequence are shown while composing one
+    fn update_zwj_next(&mut self) {
+        self.zwj_next = (self.zwj && !self.compose.is_empty())
+            .then(|| Arc::new(data::zwj_next(&self.compose_text())));
+    }
+
+    // skin tones, the hair styles which continue the sequence,
+    // and whether the sequence is recommended, i.e. widely supported
+    fn zwj_composer(&self, spacing: u16) -> Element<Message> {
+        let component_btn = |component: char, message| {
+            widget::text(component.to_string())
+                .font(self.font_family)
+                .shaping(cosmic::iced_core::text::Shaping::Advanced)
+                .size(20)
+                .apply(widget::button)
+                .style(cosmic::theme::Button::Icon)
+                .padding(EMOJI_PADDING)
+                .on_press(message)
+        };
+        let mut row =
+            widget::row::with_capacity(data::SKIN_TONES.len() + data::HAIR_STYLES.len() + 1)
+                .spacing(spacing)
+                .align_items(Alignment::Center);
+        for skin_tone in data::SKIN_TONES {
+            row = row.push(component_btn(
+                skin_tone,
+                Message::ComposeSkinTone(skin_tone),
+            ));
+        }
+        let zwj_next = self.zwj_next.as_deref();
+        for hair_style in data::HAIR_STYLES {
+            if zwj_next.is_some_and(|next| next.contains(&hair_style.to_string())) {
+                row = row.push(component_btn(
+                    hair_style,
+                    Message::ComposeHairStyle(hair_style),
+                ));
+            }
+        }
+        let text = self.compose_text();
+        let status = if !data::is_rgi(&text) {
+            fl!("zwj-invalid")
+        } else if let Some(emoji) = data::get(&text) {
+            self.emoji_name_localized(emoji).to_string()
+        } else {
+            fl!("zwj-valid")
+        };
+        row.push(widget::text::caption(status)).into()
+    }
+
+    fn last_used_with(&self, last_used: &[String], emojis: &[&str]) -> Vec<String> {
+        let mut last_used = last_used.to_vec();
+        for emoji in emojis {
+            if let Some(idx) = last_used.iter().position(|e| e == emoji) {
+                last_used.swap(0, idx);
+            } else {
+                last_used.insert(0, emoji.to_string());
+            }
+        }
+        last_used.truncate(self.config.last_used_limit);
+        last_used
+    }
+
+    // copies `contents` and closes the popup or shows that `emoji` was copied,
+    // and runs the post-copy command
+    fn copy_and_close(
+        &mut self,
+        contents: clipboard::Contents,
+        emoji: String,
+        name: String,
+    ) -> Command<cosmic::app::Message<Message>> {
+        let close = self.config.close_on_copy;
+        let backends = self.config.clipboard_backends();
+        let request = clipboard::Request::new(contents, self.config.copy_target, backends, close);
+        let mut commands = vec![self.copy(request)];
+        if !self.config.post_copy_command.is_empty() {
+            let post_copy =
+                hook::post_copy(self.config.post_copy_command.clone(), emoji.clone(), name);
+            commands.push(Command::perform(post_copy, |res| {
+                cosmic::app::Message::App(Message::PostCopy(res))
+            }));
+        }
+        if !close {
+            self.toast = Some(emoji);
+            self.toast_id = self.toast_id.wrapping_add(1);
+            let id = self.toast_id;
+            // the toast is not animated, the timeline would redraw every frame until it expires
+            commands.push(Command::perform(
+                tokio::time::sleep(TOAST_DURATION),
+                move |_| cosmic::app::Message::App(Message::ToastExpired(id)),
+            ));
+        }
+        Command::batch(commands)
+    }
+
+    // loads the symbols unless they are loaded with the current config
+    fn load_symbols(&self) -> Command<cosmic::app::Message<Message>> {
+        let dir = self.config.ucd_dir.clone();
+        let blocks = self.config.symbol_blocks.clone();
+        if symbol::is_loaded(&dir, &blocks) {
+            return Command::none();
+        }
+        let load = tokio::task::spawn_blocking(move || symbol::init(&dir, &blocks));
+        Command::perform(load, |_| cosmic::app::Message::App(Message::SymbolsLoaded))
+    }
+
+    // loads the character names for the inspector unless they are loaded from `ucd_dir`
+    fn load_names(&self) -> Command<cosmic::app::Message<Message>> {
+        let dir = self.config.ucd_dir.clone();
+        if inspect::names_loaded(&dir) {
+            return Command::none();
+        }
+        let load = tokio::task::spawn_blocking(move || inspect::load_names(&dir));
+        Command::perform(load, |_| cosmic::app::Message::App(Message::NamesLoaded))
+    }
+
+    // runs the external backends one after another without blocking, see `Message::Copied`,
+    // the popup is closed once the iced clipboard is written, so that the write is not lost
+    fn copy(&mut self, mut request: clipboard::Request) -> Command<cosmic::app::Message<Message>> {
+        let (iced, run) = request.advance();
+        let mut commands: Vec<_> = iced
+            .into_iter()
+            .map(|selection| self.iced_copy(&request.contents, selection))
+            .collect();
+        match run {
+            Some((backend, selection)) => {
+                let copy = request.copy(backend, selection, self.config.wl_copy_path.clone());
+                commands.push(Command::perform(copy, |(request, res)| {
+                    cosmic::app::Message::App(Message::Copied(request, res))
+                }));
+            }
+            None if request.close => {
+                if let Some(p) = self.popup.take() {
+                    commands.push(destroy_popup(p));
+                }
+            }
+            None => {}
+        }
+        Command::batch(commands)
+    }
+
+    // the html is offered alongside the text
+    fn iced_copy(
+        &self,
+        contents: &clipboard::Contents,
+        selection: clipboard::Selection,
+    ) -> Command<cosmic::app::Message<Message>> {
+        use clipboard::{Contents, Selection};
+        match (contents, selection) {
+            (Contents::Text { text, html }, Selection::Clipboard) => {
+                match html.as_ref().filter(|_| self.config.copy_html) {
+                    Some(html) => {
+                        iced::clipboard::write_data(clipboard::Data::text(text, Some(html)))
+                    }
+                    None => iced::clipboard::write(text.clone()),
+                }
+            }
+            (Contents::Text { text, .. }, Selection::Primary) => {
+                iced::clipboard::write_primary(text.clone())
+            }
+            (Contents::Data(data), _) => iced::clipboard::write_data(data.clone()),
+        }
+    }
+
+    fn groups(&self) -> impl Iterator<Item = Group> + '_ {
+        emojis::Group::iter()
+            .map(Group::Emoji)
+            .chain(iter::once(Group::Kaomoji))
+            .chain((!self.config.snippets.is_empty()).then_some(Group::Custom))
+            .chain((0..self.packs.len()).map(Group::Pack))
+            .chain((0..self.config.collections.len()).map(Group::Collection))
+    }
+
+    // all groups in tab order, starting with no group selected
+    fn groups_cycle(&self) -> Vec<Option<Group>> {
+        iter::once(None).chain(self.groups().map(Some)).collect()
+    }
+
+    fn collection_emojis(&self) -> Vec<String> {
+        match self.selected_group {
+            Some(Group::Collection(idx)) => self
+                .config
+                .collections
+                .get(idx)
+                .map(|collection| collection.emojis.clone())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn pack_emojis(&self) -> Vec<&'static data::Emoji> {
+        match self.selected_group {
+            Some(Group::Pack(idx)) => self
+                .packs
+                .get(idx)
+                .map(|pack| pack.emojis.clone())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn load_packs(&self) -> Command<cosmic::app::Message<Message>> {
+        let packs = async {
+            tokio::task::spawn_blocking(pack::reload)
+                .await
+                .unwrap_or_else(|err| {
+                    eprintln!("failed to load emoji packs: {err}");
+                    Vec::new()
+                })
+        };
+        Command::perform(packs, |packs| {
+            cosmic::app::Message::App(Message::PacksLoaded(packs))
+        })
+    }
+
+    fn group_icon(&self, group: Group, size: u16) -> Element<Message> {
+        match group {
+            Group::Emoji(group) => widget::icon::from_name(group_icon(group))
+                .symbolic(true)
+                .size(size)
+                .into(),
+            Group::Kaomoji => widget::text("ツ")
+                .size(size)
+                .width(size)
+                .height(size)
+                .horizontal_alignment(alignment::Horizontal::Center)
+                .vertical_alignment(alignment::Vertical::Center)
+                .into(),
+            Group::Custom => widget::icon::from_name("user-bookmarks-symbolic")
+                .size(size)
+                .into(),
+            Group::Pack(idx) => match self.packs.get(idx).and_then(|pack| pack.icon.clone()) {
+                Some(icon) => widget::icon(widget::icon::from_path(icon))
+                    .size(size)
+                    .into(),
+                None => widget::icon::from_name("folder-pictures-symbolic")
+                    .size(size)
+                    .into(),
+            },
+            Group::Collection(idx) => {
+                let icon = self
+                    .config
+                    .collections
+                    .get(idx)
+                    .map_or("", |collection| collection.icon.as_str());
+                if icon.is_empty() || data::get(icon).is_some() {
+                    widget::text(icon)
+                        .font(self.font_family)
+                        .shaping(cosmic::iced_core::text::Shaping::Advanced)
+                        .size(size)
+                        .width(size)
+                        .height(size)
+                        .horizontal_alignment(alignment::Horizontal::Center)
+                        .vertical_alignment(alignment::Vertical::Center)
+                        .into()
+                } else {
+                    widget::icon::from_name(icon).size(size).into()
+                }
+            }
+        }
+    }
+
+    fn group_string(&self, group: Group) -> String {
+        match group {
+            Group::Emoji(group) => group_string(group),
+            Group::Kaomoji => fl!("kaomoji"),
+            Group::Custom => fl!("custom"),
+            Group::Pack(idx) => self
+                .packs
+                .get(idx)
+                .map(|pack| pack.name.clone())
+                .unwrap_or_default(),
+            Group::Collection(idx) => self
+                .config
+                .collections
+                .get(idx)
+                .map(|collection| collection.name.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    // hides emojis the font cannot render, emojis newer than the maximum version
+    // and emojis which do not continue the composed ZWJ sequence
+    fn visible_filter(&self) -> impl Fn(&data::Emoji) -> bool {
+        let unsupported =
+            (self.config.unsupported_emojis == Unsupported::Hide).then(|| self.unsupported.clone());
+        let max_version = self.query.max_version(&self.config.max_unicode_version);
+        let zwj_next = self.zwj_next.clone();
+        move |emoji| {
+            unsupported
+                .as_ref()
+                .map_or(true, |unsupported| !unsupported.contains(emoji.as_str()))
+                && max_version
+                    .zip(emoji.unicode_version())
+                    .map_or(true, |(max_version, version)| version <= max_version)
+                && zwj_next.as_ref().map_or(true, |zwj_next| {
+                    zwj_next.contains(&data::strip_variation_selectors(emoji.as_str()))
+                })
+        }
+    }
+
+    fn check_unsupported(&self) -> Command<cosmic::app::Message<Message>> {
+        if self.config.unsupported_emojis == Unsupported::Show {
+            return Command::none();
+        }
+        let font_family = self.config.font_family.clone();
+        let unsupported = async move {
+            tokio::task::spawn_blocking(move || font::unsupported(&font_family))
+                .await
+                .map_err(std::io::Error::other)
+                .and_then(|res| res)
+                .unwrap_or_else(|err| {
+                    eprintln!("failed to check which emojis the font supports: {err}");
+                    HashSet::new()
+                })
+        };
+        Command::perform(unsupported, |unsupported| {
+            cosmic::app::Message::App(Message::Unsupported(Arc::new(unsupported)))
+        })
+    }
+
+    // the blocks of symbols are always shown
+    fn show_subgroups(&self) -> bool {
+        (self.config.show_subgroups || self.mode == Mode::Symbol)
+            && self.query.text.is_empty()
+            && !matches!(self.selected_group, Some(Group::Collection(_)))
+    }
+
+    /// The search text in every `letters::Style`, copied on click.
+    fn generated(&self, spacing: u16) -> Element<Message> {
+        let mut styles = widget::column::with_capacity(letters::Style::ALL.len()).spacing(spacing);
+        for style in letters::Style::ALL {
+            let generated = style.generate(&self.search);
+            let generated_txt = widget::text(generated.clone())
+                .font(self.font_family)
+                .shaping(cosmic::iced_core::text::Shaping::Advanced)
+                .size(25);
+            let style_content = widget::column::with_capacity(2)
+                .push(widget::text::caption(style.name()))
+                .push(generated_txt);
+            let style_btn = widget::button(style_content)
+                .style(cosmic::theme::Button::Icon)
+                .width(Length::Fill)
+                .padding(spacing)
+                .on_press(Message::CopyText(generated));
+            styles = styles.push(style_btn);
+        }
+        styles
+            .apply(widget::scrollable)
+            .apply(widget::container)
+            .width(Length::Fill)
+            .height(500)
+            .into()
+    }
+
+    fn update_emojified(&mut self) {
+        if self.mode != Mode::Emojify {
+            self.emojified.clear();
+            return;
+        }
+        let index = self
+            .emojify_index
+            .get_or_insert_with(|| emojify::Index::new(&self.annotations));
+        self.emojified = emojify::suggest(index, &self.search);
+    }
+
+    /// The emojified text and a toggle for each suggestion.
+    fn emojify_view(&self, spacing: u16) -> Element<Message> {
+        let rendered = emojify::render(&self.emojified);
+        let copy_btn = widget::button(widget::text(fl!("copy")))
+            .style(cosmic::theme::Button::Suggested)
+            .on_press(Message::CopyText(rendered.clone()));
+        let result = widget::row::with_capacity(2)
+            .spacing(spacing)
+            .align_items(Alignment::Center)
+            .push(
+                widget::text(rendered)
+                    .font(self.font_family)
+                    .shaping(cosmic::iced_core::text::Shaping::Advanced)
+                    .width(Length::Fill),
+            )
+            .push(copy_btn);
+        let mut suggestions = widget::column().spacing(spacing);
+        for (idx, segment) in self.emojified.iter().enumerate() {
+            let Some(emoji) = segment.suggestion else {
+                continue;
+            };
+            let suggestion = widget::row::with_capacity(2)
+                .spacing(spacing)
+                .align_items(Alignment::Center)
+                .push(widget::text(segment.text.as_str()).width(Length::Fill))
+                .push(
+                    widget::text(emoji.as_str())
+                        .font(self.font_family)
+                        .shaping(cosmic::iced_core::text::Shaping::Advanced)
+                        .size(25),
+                )
+                .apply(widget::button)
+                .style(cosmic::theme::Button::Icon)
+                .selected(segment.accepted)
+                .width(Length::Fill)
+                .padding(spacing)
+                .on_press(Message::EmojifyToggle(idx));
+            suggestions = suggestions.push(suggestion);
+        }
+        let suggestions = suggestions
+            .apply(widget::scrollable)
+            .apply(widget::container)
+            .width(Length::Fill)
+            .height(450);
+        widget::column::with_capacity(2)
+            .spacing(spacing)
+            .push(result)
+            .push(suggestions)
+            .into()
+    }
+
+    fn update_inspected(&mut self) {
+        self.inspected = if self.mode == Mode::Inspect {
+            inspect::inspect(&self.search)
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// Each character of the search text with its name and codepoints, copied on click.
+    fn inspect_view(&self, spacing: u16) -> Element<Message> {
+        let mut clusters = widget::column::with_capacity(self.inspected.len()).spacing(spacing);
+        for cluster in &self.inspected {
+            let glyph = widget::text(cluster.glyph())
+                .font(self.font_family)
+                .shaping(cosmic::iced_core::text::Shaping::Advanced)
+                .size(25)
+                .width(35)
+                .horizontal_alignment(alignment::Horizontal::Center);
+            let name = match (cluster.emoji, cluster.invisible, &cluster.name) {
+                (Some(emoji), ..) => self.emoji_name_localized(emoji).to_string(),
+                (None, Some(invisible), _) => invisible.to_string(),
+                (None, None, Some(name)) => name.clone(),
+                (None, None, None) => fl!("inspect-unknown"),
+            };
+            let mut badges = Vec::new();
+            if cluster.zwj {
+                badges.push(fl!("inspect-zwj"));
+            }
+            for &tone in &cluster.skin_tones {
+                if let Some(tone) = data::get(&tone.to_string()) {
+                    badges.push(self.emoji_name_localized(tone).to_string());
+                }
+            }
+            match cluster.presentation {
+                Some(Presentation::Emoji) => badges.push(fl!("inspect-emoji-presentation")),
+                Some(Presentation::Text) => badges.push(fl!("inspect-text-presentation")),
+                _ => {}
+            }
+            if let Some(version) = cluster.emoji.and_then(|emoji| emoji.unicode_version()) {
+                badges.push(format!("E{}.{}", version.major(), version.minor()));
+            }
+            let mut details = widget::row::with_capacity(badges.len()).spacing(spacing);
+            for badge in badges {
+                let badge = widget::text::caption(badge)
+                    .apply(widget::container)
+                    .padding([0, spacing])
+                    .style(cosmic::theme::Container::Card);
+                details = details.push(badge);
+            }
+            let description = widget::column::with_capacity(3)
+                .width(Length::Fill)
+                .push(widget::text::heading(name))
+                .push(widget::text::caption(format::codepoints(&cluster.text)))
+                .push(details);
+            let cluster_btn = widget::row::with_capacity(2)
+                .spacing(spacing)
+                .align_items(Alignment::Center)
+                .push(glyph)
+                .push(description)
+                .apply(widget::button)
+                .style(cosmic::theme::Button::Icon)
+                .width(Length::Fill)
+                .padding(spacing)
+                .on_press(Message::CopyText(cluster.text.clone()));
+            clusters = clusters.push(cluster_btn);
+        }
+        clusters
+            .apply(widget::scrollable)
+            .apply(widget::container)
+            .width(Length::Fill)
+            .height(500)
+            .into()
+    }
+
+    fn subgroup_header(&self, subgroup: &'static str, spacing: u16) -> Element<Message> {
+        let icon = if self.collapsed_subgroups.contains(subgroup) {
+            "go-next-symbolic"
+        } else {
+            "go-down-symbolic"
+        };
+        widget::row::with_capacity(2)
+            .spacing(spacing)
+            .align_items(Alignment::Center)
+            .push(widget::icon::from_name(icon).size(16))
+            .push(widget::text::heading(subgroup_string(subgroup)))
+            .apply(widget::button)
+            .style(cosmic::theme::Button::Text)
+            .width(Length::Fill)
+            .height(SUBGROUP_HEIGHT)
+            .on_press(Message::ToggleSubgroup(subgroup))
+            .into()
+    }
+
+    // the scroll offset of each subgroup header, mirroring the layout in `view_window`
+    fn subgroup_offsets(&self) -> Vec<f32> {
+        if !self.show_subgroups() {
+            return Vec::new();
+        }
+        let search_regex = self.search_regex();
+        let search_filter = self.search_filter();
+        let favorites: Vec<_> = self
+            .config_emoji_iter(&search_filter, &search_regex)
+            .collect();
+        let mut offset = rows_height(&favorites);
+        if !favorites.is_empty() {
+            let space_xs = self.core.system_theme().cosmic().spacing.space_xs;
+            // two spaces and the divider
+            offset += 2.0 * f32::from(space_xs) + 1.0;
+        }
+        let mut offsets = Vec::new();
+        for (subgroup, emojis) in subgroups(self.emoji_iter(&search_filter, &search_regex)) {
+            if !subgroup.is_empty() {
+                offsets.push(offset);
+                offset += SUBGROUP_HEIGHT;
+            }
+            if !self.collapsed_subgroups.contains(subgroup) {
+                offset += rows_height(&emojis);
+            }
+        }
+        offsets
+    }
+
+    fn jump_subgroup(&mut self, next: bool) -> Command<cosmic::app::Message<Message>> {
+        let offsets = self.subgroup_offsets();
+        if offsets.is_empty() {
+            return Command::none();
+        }
+        let idx = match self.current_subgroup {
+            Some(idx) if next => (idx + 1).min(offsets.len() - 1),
+            Some(idx) => idx.saturating_sub(1),
+            None => 0,
+        };
+        self.current_subgroup = Some(idx);
+        scrollable::scroll_to(
+            self.scrollable_id.clone(),
+            scrollable::AbsoluteOffset {
+                x: 0.0,
+                y: offsets[idx],
+            },
+        )
+    }
+
+    fn search_regex(&self) -> Option<regex::Regex> {
+        // use regex to apply simple unicode case folding
+        let regex_pattern = regex::escape(&self.query.text);
+        RegexBuilder::new(&regex_pattern)
+            .case_insensitive(true)
+            .build()
+            .ok()
+    }
+
     fn search_filter<'a>(
         &'a self,
-    ) -> impl for<'b> Fn(&'static emojis::Emoji, Option<&'b regex::Regex>) -> bool + 'a {
-        let search_filter = |emoji: &'static emojis::Emoji, search_regex: Option<&regex::Regex>| {
-            if self.search.is_empty() {
+    ) -> impl for<'b> Fn(&'static data::Emoji, Option<&'b regex::Regex>) -> bool + 'a {
+        let search_filter = |emoji: &'static data::Emoji, search_regex: Option<&regex::Regex>| {
+            if self.query.text.is_empty() {
                 return true;
             }
             let emoji_name = self.emoji_name_localized(&emoji);
-            match search_regex {
-                Some(re) => re.is_match(emoji_name),
-                None => emoji_name.contains(&self.search),
+            let is_match = |s: &str| match search_regex {
+                Some(re) => re.is_match(s),
+                None => s.contains(&self.query.text),
+            };
+            if is_match(emoji_name) || emoji.keywords().iter().any(|keyword| is_match(keyword)) {
+                return true;
             }
+            // flags are also found by their ISO code and region
+            emoji.group() == Some(emojis::Group::Flags)
+                && (flag::matches_code(emoji.as_str(), &self.query.text)
+                    || flag::region(emoji.as_str()).is_some_and(|region| is_match(&region.name())))
         };
         search_filter
     }
@@ -588,7 +1935,28 @@ fn chunks<T, const N: usize>(
     })
 }
 
-fn group_from_key(key: u8) -> Option<emojis::Group> {
+// the height of the rows `emoji_rows` in `view_window` creates
+fn rows_height(emojis: &[&data::Emoji]) -> f32 {
+    let texts = emojis.iter().filter(|emoji| emoji.is_wide()).count();
+    let rows = (emojis.len() - texts).div_ceil(GRID_SIZE) + texts.div_ceil(TEXT_GRID_SIZE);
+    rows as f32 * ROW_HEIGHT
+}
+
+// consecutive emojis of the same subgroup
+fn subgroups(
+    emojis: impl Iterator<Item = &'static data::Emoji>,
+) -> Vec<(&'static str, Vec<&'static data::Emoji>)> {
+    let mut subgroups: Vec<(&'static str, Vec<_>)> = Vec::new();
+    for emoji in emojis {
+        match subgroups.last_mut() {
+            Some((subgroup, emojis)) if *subgroup == emoji.subgroup() => emojis.push(emoji),
+            _ => subgroups.push((emoji.subgroup(), vec![emoji])),
+        }
+    }
+    subgroups
+}
+
+fn group_from_key(key: u8) -> Option<Group> {
     use emojis::Group::*;
     let group = match key {
         b'1' => SmileysAndEmotion,
@@ -602,23 +1970,11 @@ fn group_from_key(key: u8) -> Option<emojis::Group> {
         b'9' => Flags,
         _ => return None,
     };
-    return Some(group);
+    return Some(Group::Emoji(group));
 }
-fn key_from_group(group: Option<emojis::Group>) -> u8 {
-    use emojis::Group::*;
-    let group = match group {
-        Some(SmileysAndEmotion) => b'1',
-        Some(PeopleAndBody) => b'2',
-        Some(AnimalsAndNature) => b'3',
-        Some(FoodAndDrink) => b'4',
-        Some(TravelAndPlaces) => b'5',
-        Some(Activities) => b'6',
-        Some(Objects) => b'7',
-        Some(Symbols) => b'8',
-        Some(Flags) => b'9',
-        None => b'0',
-    };
-    return group;
+// position of the group in the tab order, unknown groups fall back to no group
+fn group_position(groups: &[Option<Group>], group: Option<Group>) -> usize {
+    groups.iter().position(|g| *g == group).unwrap_or(0)
 }
 
 fn group_string(group: emojis::Group) -> String {
@@ -635,41 +1991,73 @@ fn group_string(group: emojis::Group) -> String {
     }
 }
 
-fn format_emoji(emoji: &emojis::Emoji, show_unicode: bool) -> String {
+fn subgroup_string(subgroup: &str) -> String {
+    let message_id = format!("subgroup-{}", subgroup.replace(" & ", "-and-"));
+    if LANGUAGE_LOADER.has(&message_id) {
+        return LANGUAGE_LOADER.get(&message_id);
+    }
+    // `face-smiling` -> `Face smiling`
+    let mut name = subgroup.replace('-', " ");
+    if let Some(first) = name.get_mut(..1) {
+        first.make_ascii_uppercase();
+    }
+    name
+}
+
+fn format_emoji(emoji: &data::Emoji, show_unicode: bool) -> String {
     return if !show_unicode {
         emoji.name().to_string()
     } else {
-        format!(
-            "{} - {}",
-            emoji.name(),
-            emoji
-                .as_str()
-                .chars()
-                .map(|c| format!("U+{:X}", c as u32))
-                .collect::<Vec<_>>()
-                .join(" ")
-        )
+        format!("{} - {}", emoji.name(), format::codepoints(emoji.as_str()))
     };
 }
 
 fn navigation_subscription() -> Subscription<Message> {
     use cosmic::iced::event;
     cosmic::iced_futures::event::listen_with(|event, status| {
-        if status == event::Status::Captured {
-            return None;
-        }
+        let captured = status == event::Status::Captured;
         let event::Event::Keyboard(key_event) = event else {
             return None;
         };
-
-        let cosmic::iced_runtime::keyboard::Event::KeyReleased { key, .. } = key_event else {
+        // the search input captures Backspace while it has focus, even when it is empty
+        if let cosmic::iced_runtime::keyboard::Event::KeyPressed {
+            key:
+                cosmic::iced_runtime::keyboard::Key::Named(
+                    cosmic::iced::keyboard::key::Named::Backspace,
+                ),
+            ..
+        } = key_event
+        {
+            return Some(Message::Backspace(captured));
+        }
+        if captured {
             return None;
+        }
+
+        let key = match key_event {
+            cosmic::iced_runtime::keyboard::Event::ModifiersChanged(modifiers) => {
+                return Some(Message::Modifiers(modifiers))
+            }
+            // pressed, so that a release after the search input submitted is not handled again
+            cosmic::iced_runtime::keyboard::Event::KeyPressed { key, .. } => {
+                return match key {
+                    cosmic::iced_runtime::keyboard::Key::Named(
+                        cosmic::iced::keyboard::key::Named::Enter,
+                    ) => Some(Message::Enter),
+                    _ => None,
+                };
+            }
+            cosmic::iced_runtime::keyboard::Event::KeyReleased { key, .. } => key,
         };
         match key {
             cosmic::iced_runtime::keyboard::Key::Named(key_named) => match key_named {
                 cosmic::iced::keyboard::key::Named::Escape => return Some(Message::Exit),
                 cosmic::iced::keyboard::key::Named::ArrowRight => return Some(Message::ArrowRight),
                 cosmic::iced::keyboard::key::Named::ArrowLeft => return Some(Message::ArrowLeft),
+                cosmic::iced::keyboard::key::Named::PageDown => return Some(Message::NextSubgroup),
+                cosmic::iced::keyboard::key::Named::PageUp => {
+                    return Some(Message::PreviousSubgroup)
+                }
                 cosmic::iced::keyboard::key::Named::End => {
                     return Some(Message::ScrollToPercent(1))
                 }